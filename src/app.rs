@@ -1,71 +1,81 @@
 use anyhow::{Context, Result};
-use rusqlite::{Connection, params, params_from_iter};
 use std::{
     collections::{HashMap, HashSet},
-    time::Duration,
+    io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
 };
 use tui_widget_list::ListState;
+use unicode_width::UnicodeWidthStr;
 
 use ratatui::{
-    Frame,
-    crossterm::event::{self, Event, KeyCode, KeyModifiers},
-    layout::{Constraint, Layout, Rect},
+    DefaultTerminal, Frame,
+    crossterm::{
+        event::{
+            self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, MouseButton, MouseEvent,
+            MouseEventKind,
+        },
+        execute,
+        terminal::{self, EnterAlternateScreen},
+    },
+    layout::{Constraint, Layout, Position, Rect},
     style::Stylize,
-    widgets::Paragraph,
+    text::{Line, Span},
+    widgets::{Paragraph, Tabs},
 };
 
 use crate::{
-    color_scheme::COLOR_SCHEME,
+    calendar_widget::{self, Calendar, CalendarState},
+    color_scheme::{self, colors},
+    command::{Arg, CommandLine},
+    config::{PreviewPosition, config},
+    db::Db,
+    editor::{self, EditTarget},
     error_widget::ErrorWidget,
+    help_widget::{HelpState, HelpWidget},
+    interop::{self, Format},
+    keymap::{Action, Scope, keymap},
     lane_widget::{LaneState, LaneWidget},
-    model::{Message, Model, RunningState, SelectedPane, Task, TaskState},
+    model::{Message, Model, RunningState, SelectedPane, Task, TaskState, View},
+    preview_widget::PreviewWidget,
     selectlist_widget::{SelectList, SelectListState},
+    state::State,
+    stats::Stats,
+    stats_widget::StatsWidget,
+    swimlanes::Swimlanes,
+    table_widget::{Column, TaskTable, TaskTableState},
+    workspace::{display_path, expand_home},
 };
 
-const ARCHIVE_TAG: &'static str = "Archive";
+// Lines scrolled in help by PgUp and PgDn
+const HELP_PAGE: isize = 10;
+// Max interval between clicks on the same task to open it
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+// keys lists keys bound to given actions, for hints
+fn keys(actions: &[Action]) -> String {
+    let keys: Vec<_> = actions
+        .iter()
+        .flat_map(|a| keymap().keys(*a))
+        .map(|k| format!("<{k}>"))
+        .collect();
+    keys.join("/")
+}
 
 pub struct App<'a> {
     model: Model<'a>,
-    db: rusqlite::Connection,
+    db: Db,
+    db_path: PathBuf,
 }
 
 impl<'a> App<'a> {
-    pub fn load(db_path: &str) -> Result<Self> {
+    pub fn load(db_path: &Path) -> Result<Self> {
+        color_scheme::init().context("loading color themes")?;
         let mut tasks = HashMap::new();
-        let db = match Connection::open(db_path).context("opening database") {
-            Ok(db) => db,
-            Err(_) => Connection::open("awdy.db").context("opening database")?,
-        };
-
-        {
-            db.execute(
-                "CREATE TABLE IF NOT EXISTS tasks (
-                id INTEGER PRIMARY KEY,
-                state INTEGER NOT NULL,
-                title TEXT NOT NULL,
-                description TEXT
-            )",
-                (),
-            )
-            .context("initializing database")?;
-            db.execute(
-                "CREATE TABLE IF NOT EXISTS tags (
-                tag TEXT NOT NULL,
-                task_id INTEGER NOT NULL,
-                PRIMARY KEY (tag, task_id)
-            )",
-                (),
-            )
-            .context("initializing database")?;
-        }
+        let db = Db::open(db_path)?;
 
         let mut lanes = Vec::with_capacity(4);
-        for state in [
-            TaskState::Todo,
-            TaskState::InProgress,
-            TaskState::Blocked,
-            TaskState::Done,
-        ] {
+        for state in TaskState::ALL {
             tasks.insert(state, Vec::new());
             lanes.push(LaneState::new());
         }
@@ -73,11 +83,13 @@ impl<'a> App<'a> {
         let tags_list = SelectListState {
             list_state: ListState::default(),
             items: Vec::new(),
+            area: Rect::default(),
         };
 
         lanes[0].selected = true;
         let mut r = Self {
             db,
+            db_path: db_path.to_path_buf(),
             model: Model {
                 active_lane: 0,
                 active_pane: SelectedPane::Lanes,
@@ -86,18 +98,52 @@ impl<'a> App<'a> {
                 tasks,
                 lanes,
                 task_view: None,
+                help: None,
+                command_line: None,
+                drag: None,
+                last_click: None,
+                edit_request: None,
+                status: None,
+                state: State::load(),
+                swimlanes: (!config().swimlanes.is_empty())
+                    .then(|| Swimlanes::new(config().swimlanes.clone())),
+                view: View::Board,
+                table: TaskTableState::new(),
+                calendar: CalendarState::new(),
+                stats: None,
+                show_tags: None,
+                tags_visible: true,
+                width: 0,
                 last_error: None,
+                db_path: display_path(db_path),
             },
         };
 
-        r.update_filtered_tasks()?;
         r.update_tags()?;
+        for (tag, selected, _) in &mut r.model.tags.items {
+            *selected = config().default_filters.contains(tag);
+        }
+        r.update_filtered_tasks()?;
 
         Ok(r)
     }
 
     pub fn run(mut self) -> Result<()> {
         let mut terminal = ratatui::try_init()?;
+        // terminal is restored on every way out of the loop, errors included
+        let result = self.run_loop(&mut terminal);
+        if config().mouse {
+            let _ = execute!(io::stdout(), DisableMouseCapture);
+        }
+        ratatui::restore();
+        result
+    }
+
+    fn run_loop(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        if config().mouse {
+            execute!(io::stdout(), EnableMouseCapture)?;
+        }
+        self.update(Message::Resize(terminal.size()?.width));
 
         while self.model.running_state != RunningState::Done {
             // Render the current view
@@ -110,10 +156,39 @@ impl<'a> App<'a> {
             while current_msg.is_some() {
                 current_msg = self.update(current_msg.unwrap());
             }
+
+            if let Some(target) = self.model.edit_request.take() {
+                self.run_editor(terminal, target)?;
+            }
+        }
+        Ok(())
+    }
+
+    // run_editor suspends TUI while task is edited in external editor
+    fn run_editor(&mut self, terminal: &mut DefaultTerminal, target: EditTarget) -> Result<()> {
+        let Some(task_view) = self.model.task_view.as_mut() else {
+            return Ok(());
+        };
+        let text = task_view.edit_text(target);
+
+        if config().mouse {
+            execute!(io::stdout(), DisableMouseCapture)?;
         }
         ratatui::restore();
+        let edited = editor::edit(&text);
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen)?;
+        if config().mouse {
+            execute!(io::stdout(), EnableMouseCapture)?;
+        }
+        terminal.clear()?;
+
+        if let Err(e) = edited.and_then(|text| task_view.apply_edit(target, text)) {
+            self.model.last_error = Some(e);
+        }
         Ok(())
     }
+
     fn view(&mut self, frame: &mut Frame) {
         let layout =
             Layout::vertical([Constraint::Fill(1), Constraint::Max(1)]).split(frame.area());
@@ -123,6 +198,9 @@ impl<'a> App<'a> {
             RunningState::TaskView => self.task_view(frame, layout[0]),
             RunningState::Done => {}
         }
+        if self.model.help.is_some() {
+            self.show_help(frame, layout[0]);
+        }
         if self.model.last_error.is_some() {
             self.show_error(frame, layout[0]);
         }
@@ -130,58 +208,249 @@ impl<'a> App<'a> {
 
     fn status_bar(&self, frame: &mut Frame, area: Rect) {
         let text = match self.model.running_state {
-            RunningState::MainView => {
-                "Hint: use Tab and Shift+Tab to move between panes, arrows or hjkl for navigation. Move task between panes using keys 1,2,3,4. Enter opens task, <n> creates new task, <a> archives task"
-            }
-            RunningState::TaskView => {
-                "Hint: Esc to close without saving, Ctrl+S to save and close. Tags are comma-separated"
+            _ if let Some(status) = &self.model.status => status.clone(),
+            RunningState::MainView if self.model.view == View::Calendar => {
+                format!(
+                    "Hint: {} day, {} week, {} month, {} tasks of the day, {} open task, {} month or week view, {} board",
+                    keys(&[Action::PrevLane, Action::NextLane]),
+                    keys(&[Action::Up, Action::Down]),
+                    keys(&[Action::PrevMonth, Action::NextMonth]),
+                    keys(&[Action::NextPane]),
+                    keys(&[Action::OpenTask]),
+                    keys(&[Action::ToggleCalendarWeek]),
+                    keys(&[Action::ToggleCalendar]),
+                )
             }
+            RunningState::MainView if self.model.view == View::Stats => format!(
+                "Hint: {} board, {} table, {} calendar, {} command line, {} quit",
+                keys(&[Action::ToggleStats]),
+                keys(&[Action::ToggleTable]),
+                keys(&[Action::ToggleCalendar]),
+                keys(&[Action::Command]),
+                keys(&[Action::Quit]),
+            ),
+            RunningState::MainView => format!(
+                "Hint: {}",
+                keymap().hint(Scope::Main, &self.model.active_pane)
+            ),
+            RunningState::TaskView => format!(
+                "Hint: {}. Tags are comma-separated",
+                keymap().hint(Scope::Task, &self.model.active_pane)
+            ),
             RunningState::Done => return,
         };
 
-        let c = Paragraph::new(text)
-            .bg(COLOR_SCHEME.status_bar_bg)
-            .fg(COLOR_SCHEME.status_bar_fg);
-        frame.render_widget(c, area);
+        let db = format!(" {} ", self.model.db_path);
+        let [hint_area, db_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(db.width() as u16)])
+                .areas(area);
+        let colors = colors();
+        match &self.model.command_line {
+            Some(cmd) => {
+                let candidates = cmd.candidates.join(" ");
+                let line = Line::from(vec![
+                    Span::raw(format!(":{}", cmd.input)),
+                    Span::raw("  "),
+                    Span::raw(candidates).italic(),
+                ]);
+                frame.render_widget(Paragraph::new(line).style(colors.text()), hint_area);
+                frame
+                    .set_cursor_position((hint_area.x + 1 + cmd.input.width() as u16, hint_area.y));
+            }
+            None => {
+                let c = Paragraph::new(text).style(colors.status_bar());
+                frame.render_widget(c, hint_area);
+            }
+        }
+        let c = Paragraph::new(db).style(colors.status_bar().bold());
+        frame.render_widget(c, db_area);
     }
 
     fn main_view(&mut self, frame: &mut Frame, area: Rect) {
-        let panes =
-            Layout::horizontal([Constraint::Percentage(10), Constraint::Fill(1)]).split(area);
-        let lane_areas = Layout::horizontal([
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
-        ])
-        .split(panes[1]);
-
-        frame.render_stateful_widget(
-            &SelectList {
-                title: "Tags".to_string(),
-                inactive: self.model.active_pane != SelectedPane::Tags,
-            },
-            panes[0],
-            &mut self.model.tags,
-        );
+        let layout = &config().layout;
+        // panes that are not shown must not catch mouse clicks
+        for lane in &mut self.model.lanes {
+            lane.area = Rect::default();
+        }
+        self.model.table.area = Rect::default();
+        // calendar and statistics need all the space they can get
+        match self.model.view {
+            View::Calendar => {
+                let calendar = Calendar {
+                    tasks: &self.model.tasks,
+                };
+                frame.render_stateful_widget(&calendar, area, &mut self.model.calendar);
+            }
+            View::Stats => {
+                if let Some(stats) = &self.model.stats {
+                    frame.render_widget(&StatsWidget { stats }, area);
+                }
+            }
+            View::Board | View::Table => {}
+        }
+        if matches!(self.model.view, View::Calendar | View::Stats) {
+            return;
+        }
+        let area = if self.model.state.preview {
+            let constraints = [
+                Constraint::Fill(1),
+                Constraint::Percentage(layout.preview_size),
+            ];
+            let [area, preview_area] = match layout.preview {
+                PreviewPosition::Right => Layout::horizontal(constraints).areas(area),
+                PreviewPosition::Bottom => Layout::vertical(constraints).areas(area),
+            };
+            let preview = PreviewWidget {
+                task: self.selected_task(),
+            };
+            frame.render_widget(&preview, preview_area);
+            area
+        } else {
+            area
+        };
+        let lanes_area = if self.model.tags_visible {
+            let panes = Layout::horizontal([
+                Constraint::Percentage(layout.tags_width),
+                Constraint::Fill(1),
+            ])
+            .split(area);
+            frame.render_stateful_widget(
+                &SelectList {
+                    title: "Tags".to_string(),
+                    inactive: self.model.active_pane != SelectedPane::Tags,
+                },
+                panes[0],
+                &mut self.model.tags,
+            );
+            panes[1]
+        } else {
+            area
+        };
 
-        for ((idx, mut lane), area) in self
-            .model
-            .lanes
-            .iter_mut()
-            .enumerate()
-            .zip(lane_areas.iter())
-        {
-            let state = TaskState::from(idx as i32);
-            let lane_widget = LaneWidget {
-                title: state.into(),
+        let single_lane = frame.area().width < layout.single_lane_width;
+        if self.model.swimlanes.is_some() && self.model.view == View::Board {
+            self.swimlanes_view(frame, lanes_area, single_lane);
+        } else if self.model.view == View::Table {
+            let table = TaskTable {
+                tasks: &self.model.tasks,
+                selected: self.selected_row(),
                 inactive: self.model.active_pane != SelectedPane::Lanes,
-                tasks: self.model.tasks.get(&state).unwrap(),
             };
-            frame.render_stateful_widget(&lane_widget, *area, &mut lane);
+            frame.render_stateful_widget(&table, lanes_area, &mut self.model.table);
+        } else if single_lane {
+            let [tabs_area, lane_area] =
+                Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(lanes_area);
+            let titles =
+                TaskState::ALL.map(|state| format!("{state} ({})", self.model.tasks[&state].len()));
+            let tabs = Tabs::new(titles)
+                .select(self.model.active_lane)
+                .style(colors().lane_title())
+                .highlight_style(colors().lane_active_title().bold());
+            frame.render_widget(tabs, tabs_area);
+            self.render_lane(frame, self.model.active_lane, lane_area);
+        } else {
+            let constraints = layout.lanes.map(Constraint::Fill);
+            let lane_areas = if layout.stacked {
+                Layout::vertical(constraints).split(lanes_area)
+            } else {
+                Layout::horizontal(constraints).split(lanes_area)
+            };
+            for (idx, area) in lane_areas.iter().enumerate() {
+                self.render_lane(frame, idx, *area);
+            }
+        }
+    }
+
+    // swimlanes_view shows board split into rows, with only active lane shown in each row if
+    // single_lane is set
+    fn swimlanes_view(&mut self, frame: &mut Frame, area: Rect, single_lane: bool) {
+        let colors = colors();
+        let area = if single_lane {
+            let [tabs_area, area] =
+                Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(area);
+            let tabs = Tabs::new(TaskState::ALL.map(|state| state.to_string()))
+                .select(self.model.active_lane)
+                .style(colors.lane_title())
+                .highlight_style(colors.lane_active_title().bold());
+            frame.render_widget(tabs, tabs_area);
+            area
+        } else {
+            area
+        };
+        let model = &mut self.model;
+        let Some(swimlanes) = model.swimlanes.as_mut() else {
+            return;
+        };
+        let constraints = swimlanes.rows.iter().map(|r| {
+            if r.collapsed {
+                Constraint::Length(1)
+            } else {
+                Constraint::Fill(1)
+            }
+        });
+        let row_areas = Layout::vertical(constraints).split(area);
+        let active = swimlanes.active;
+        let columns: Vec<usize> = if single_lane {
+            vec![model.active_lane]
+        } else {
+            (0..TaskState::ALL.len()).collect()
+        };
+        let widths: Vec<_> = columns
+            .iter()
+            .map(|&c| Constraint::Fill(config().layout.lanes[c]))
+            .collect();
+
+        for (idx, row_area) in row_areas.iter().enumerate() {
+            let [header, lanes_area] =
+                Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(*row_area);
+            let title = swimlanes.title(idx);
+            let row = &mut swimlanes.rows[idx];
+            row.area = *row_area;
+            row.header = header;
+            // active row keeps its tasks and lanes in model
+            let (tasks, lanes) = if idx == active {
+                (&model.tasks, &mut model.lanes)
+            } else {
+                (&row.tasks, &mut row.lanes)
+            };
+            let count: usize = tasks.values().map(Vec::len).sum();
+            let marker = if row.collapsed { "▸" } else { "▾" };
+            let style = if idx == active {
+                colors.lane_active_title().bold()
+            } else {
+                colors.lane_title()
+            };
+            frame.render_widget(
+                Line::styled(format!("{marker} {title} ({count})"), style),
+                header,
+            );
+            if row.collapsed {
+                continue;
+            }
+
+            let lane_areas = Layout::horizontal(widths.clone()).split(lanes_area);
+            for (&column, lane_area) in columns.iter().zip(lane_areas.iter()) {
+                let state = TaskState::from(column as i32);
+                let lane_widget = LaneWidget {
+                    title: state.into(),
+                    inactive: idx != active || model.active_pane != SelectedPane::Lanes,
+                    tasks: &tasks[&state],
+                };
+                frame.render_stateful_widget(&lane_widget, *lane_area, &mut lanes[column]);
+            }
         }
     }
 
+    fn render_lane(&mut self, frame: &mut Frame, idx: usize, area: Rect) {
+        let state = TaskState::from(idx as i32);
+        let lane_widget = LaneWidget {
+            title: state.into(),
+            inactive: self.model.active_pane != SelectedPane::Lanes,
+            tasks: self.model.tasks.get(&state).unwrap(),
+        };
+        frame.render_stateful_widget(&lane_widget, area, &mut self.model.lanes[idx]);
+    }
+
     fn task_view(&mut self, frame: &mut Frame, area: Rect) {
         self.main_view(frame, area); // draw lanes in background to keep visual context
         if let Some(v) = self.model.task_view.as_ref() {
@@ -189,6 +458,20 @@ impl<'a> App<'a> {
         }
     }
 
+    fn show_help(&mut self, frame: &mut Frame, area: Rect) {
+        let (title, scope) = match (&self.model.running_state, &self.model.active_pane) {
+            (RunningState::TaskView, _) => ("Keys: task view", Scope::Task),
+            (_, SelectedPane::Lanes) => ("Keys: lanes", Scope::Main),
+            (_, SelectedPane::Tags) => ("Keys: tags", Scope::Main),
+        };
+        let help = HelpWidget {
+            title,
+            scope,
+            pane: &self.model.active_pane,
+        };
+        frame.render_stateful_widget(help, area, self.model.help.as_mut().unwrap());
+    }
+
     fn show_error(&mut self, frame: &mut Frame, area: Rect) {
         let p = ErrorWidget {
             title: "ERROR".to_string(),
@@ -198,93 +481,298 @@ impl<'a> App<'a> {
     }
 
     fn handle_event(&self) -> Result<Option<Message>> {
-        if event::poll(Duration::from_millis(250))? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == event::KeyEventKind::Press {
-                    return Ok(self.handle_key(key));
+        if !event::poll(config().poll_interval())? {
+            return Ok(None);
+        }
+        match event::read()? {
+            Event::Key(key) if key.kind == event::KeyEventKind::Press => Ok(self.handle_key(key)),
+            Event::Mouse(mouse) => Ok(self.handle_mouse(mouse)),
+            Event::Resize(width, _) => Ok(Some(Message::Resize(width))),
+            _ => Ok(None),
+        }
+    }
+
+    fn handle_mouse(&self, mouse: MouseEvent) -> Option<Message> {
+        let pos = Position::new(mouse.column, mouse.row);
+        if self.model.last_error.is_some() {
+            return match mouse.kind {
+                MouseEventKind::Down(_) => Some(Message::CloseError),
+                _ => None,
+            };
+        }
+        if self.model.help.is_some() {
+            return match mouse.kind {
+                MouseEventKind::ScrollDown => Some(Message::ScrollHelp(1)),
+                MouseEventKind::ScrollUp => Some(Message::ScrollHelp(-1)),
+                MouseEventKind::Down(_) => Some(Message::CloseHelp),
+                _ => None,
+            };
+        }
+        if self.model.running_state != RunningState::MainView || self.model.command_line.is_some() {
+            return None;
+        }
+
+        if self.model.table.area.contains(pos) {
+            let rows = self.model.tasks.values().map(Vec::len).sum();
+            let table = &self.model.table;
+            return match mouse.kind {
+                MouseEventKind::Down(MouseButton::Left) => {
+                    if let Some(column) = table.column_at(pos) {
+                        Some(Message::SortTable(column))
+                    } else {
+                        table.row_at(pos, rows).map(Message::ClickRow)
+                    }
                 }
+                MouseEventKind::Up(MouseButton::Left) if self.model.drag.is_some() => {
+                    Some(Message::DropTask(None))
+                }
+                MouseEventKind::ScrollDown => Some(Message::SelectRow(1)),
+                MouseEventKind::ScrollUp => Some(Message::SelectRow(-1)),
+                _ => None,
+            };
+        }
+        if let Some(swimlanes) = &self.model.swimlanes
+            && self.model.view == View::Board
+            && mouse.kind == MouseEventKind::Down(MouseButton::Left)
+        {
+            if let Some(row) = swimlanes.rows.iter().position(|r| r.header.contains(pos)) {
+                return Some(Message::ToggleSwimlane(row));
+            }
+            if let Some(row) = swimlanes.rows.iter().position(|r| r.area.contains(pos))
+                && row != swimlanes.active
+            {
+                return Some(Message::SelectSwimlane(row));
+            }
+        }
+        let lane = self.model.lanes.iter().position(|l| l.area.contains(pos));
+        let tag = self.model.tags.item_at(pos);
+        match (mouse.kind, lane, tag) {
+            (MouseEventKind::Down(MouseButton::Left), Some(lane), _) => {
+                let tasks = self.model.tasks[&TaskState::from(lane as i32)].len();
+                let task = self.model.lanes[lane].item_at(pos, tasks);
+                Some(Message::ClickTask(lane, task))
+            }
+            (MouseEventKind::Down(MouseButton::Left), _, Some(tag)) => Some(Message::ClickTag(tag)),
+            (MouseEventKind::Up(MouseButton::Left), lane, _) if self.model.drag.is_some() => {
+                Some(Message::DropTask(lane))
+            }
+            (MouseEventKind::ScrollDown, Some(lane), _) => Some(Message::ScrollLane(lane, 1)),
+            (MouseEventKind::ScrollUp, Some(lane), _) => Some(Message::ScrollLane(lane, -1)),
+            (MouseEventKind::ScrollDown, _, _) if self.model.tags.area.contains(pos) => {
+                Some(Message::NextTag)
+            }
+            (MouseEventKind::ScrollUp, _, _) if self.model.tags.area.contains(pos) => {
+                Some(Message::PrevTag)
             }
+            _ => None,
         }
-        Ok(None)
     }
 
     fn handle_key(&self, key: event::KeyEvent) -> Option<Message> {
         if self.model.last_error.is_some() {
             return Some(Message::CloseError);
         }
+        if self.model.command_line.is_some() {
+            return match key.code {
+                KeyCode::Esc => Some(Message::CloseCommandLine),
+                KeyCode::Enter => Some(Message::RunCommand),
+                KeyCode::Tab => Some(Message::CompleteCommand),
+                _ => Some(Message::KeyPress(key)),
+            };
+        }
+        if self.model.help.is_some() {
+            return match (keymap().action(Scope::Main, key), key.code) {
+                (Some(Action::Down), _) => Some(Message::ScrollHelp(1)),
+                (Some(Action::Up), _) => Some(Message::ScrollHelp(-1)),
+                (_, KeyCode::PageDown) => Some(Message::ScrollHelp(HELP_PAGE)),
+                (_, KeyCode::PageUp) => Some(Message::ScrollHelp(-HELP_PAGE)),
+                _ => Some(Message::CloseHelp),
+            };
+        }
 
         match self.model.running_state {
-            RunningState::MainView => match key.code {
-                KeyCode::Char('q') => Some(Message::Quit),
-                KeyCode::Char('n') => Some(Message::NewTask),
-                KeyCode::Char('1') => Some(Message::MoveTask(TaskState::Todo)),
-                KeyCode::Char('2') => Some(Message::MoveTask(TaskState::InProgress)),
-                KeyCode::Char('3') => Some(Message::MoveTask(TaskState::Blocked)),
-                KeyCode::Char('4') => Some(Message::MoveTask(TaskState::Done)),
-                KeyCode::Char('a') => Some(Message::ToggleTaskTag(String::from(ARCHIVE_TAG))),
-                KeyCode::Right | KeyCode::Char('l') => Some(Message::NextLane),
-                KeyCode::Left | KeyCode::Char('h') => Some(Message::PrevLane),
-                KeyCode::Tab => Some(Message::NextPane),
-                KeyCode::BackTab => Some(Message::PrevPane),
-                KeyCode::Down | KeyCode::Char('j')
-                    if self.model.active_pane == SelectedPane::Lanes =>
-                {
-                    Some(Message::NextTask)
-                }
-                KeyCode::Down | KeyCode::Char('j')
-                    if self.model.active_pane == SelectedPane::Tags =>
-                {
-                    Some(Message::NextTag)
-                }
-                KeyCode::Up | KeyCode::Char('k')
-                    if self.model.active_pane == SelectedPane::Lanes =>
-                {
-                    Some(Message::PrevTask)
+            RunningState::MainView if self.model.view == View::Calendar => self.calendar_key(key),
+            RunningState::MainView if self.model.view == View::Stats => {
+                match keymap().action(Scope::Main, key)? {
+                    Action::Help => Some(Message::ShowHelp),
+                    Action::Command => Some(Message::OpenCommandLine),
+                    Action::Quit => Some(Message::Quit),
+                    Action::NextTheme => Some(Message::NextTheme),
+                    Action::ToggleTable => Some(Message::ToggleTable),
+                    Action::ToggleCalendar => Some(Message::ToggleCalendar),
+                    Action::ToggleStats => Some(Message::ToggleStats),
+                    _ => None,
                 }
-                KeyCode::Up | KeyCode::Char('k')
-                    if self.model.active_pane == SelectedPane::Tags =>
-                {
-                    Some(Message::PrevTag)
-                }
-                KeyCode::Enter | KeyCode::Char('e')
-                    if self.model.active_pane == SelectedPane::Lanes =>
-                {
-                    Some(Message::OpenTask)
+            }
+            RunningState::MainView => {
+                let lanes = self.model.active_pane == SelectedPane::Lanes;
+                let action = keymap().action(Scope::Main, key)?;
+                if action.pane().is_some_and(|p| p != self.model.active_pane) {
+                    return None;
                 }
-                KeyCode::Char(' ') if self.model.active_pane == SelectedPane::Tags => {
-                    Some(Message::ToggleTag)
+                match action {
+                    Action::Help => Some(Message::ShowHelp),
+                    Action::Command => Some(Message::OpenCommandLine),
+                    Action::Quit => Some(Message::Quit),
+                    Action::NewTask => Some(Message::NewTask),
+                    Action::MoveTodo => Some(Message::MoveTask(TaskState::Todo)),
+                    Action::MoveInProgress => Some(Message::MoveTask(TaskState::InProgress)),
+                    Action::MoveBlocked => Some(Message::MoveTask(TaskState::Blocked)),
+                    Action::MoveDone => Some(Message::MoveTask(TaskState::Done)),
+                    Action::ArchiveTask => {
+                        Some(Message::ToggleTaskTag(config().archive_tag.clone()))
+                    }
+                    Action::NextTheme => Some(Message::NextTheme),
+                    Action::TogglePreviewPane => Some(Message::TogglePreviewPane),
+                    Action::ToggleTagsPane => Some(Message::ToggleTagsPane),
+                    Action::ToggleTable => Some(Message::ToggleTable),
+                    Action::ToggleCalendar => Some(Message::ToggleCalendar),
+                    Action::ToggleStats => Some(Message::ToggleStats),
+                    Action::NextSwimlane => Some(Message::SelectSwimlane(
+                        self.model.swimlanes.as_ref()?.active + 1,
+                    )),
+                    Action::PrevSwimlane => Some(Message::SelectSwimlane(
+                        self.model.swimlanes.as_ref()?.active.saturating_sub(1),
+                    )),
+                    Action::ToggleSwimlane => Some(Message::ToggleSwimlane(
+                        self.model.swimlanes.as_ref()?.active,
+                    )),
+                    Action::SortTable => {
+                        let columns = Column::ALL;
+                        let current = columns.iter().position(|c| *c == self.model.table.sort);
+                        let next = current.map_or(0, |idx| (idx + 1) % columns.len());
+                        Some(Message::SortTable(columns[next]))
+                    }
+                    Action::ReverseSort => Some(Message::SortTable(self.model.table.sort)),
+                    Action::Down if lanes && self.model.view == View::Table => {
+                        Some(Message::SelectRow(1))
+                    }
+                    Action::Up if lanes && self.model.view == View::Table => {
+                        Some(Message::SelectRow(-1))
+                    }
+                    Action::NextLane => Some(Message::NextLane),
+                    Action::PrevLane => Some(Message::PrevLane),
+                    Action::NextPane => Some(Message::NextPane),
+                    Action::PrevPane => Some(Message::PrevPane),
+                    Action::Down if lanes => Some(Message::NextTask),
+                    Action::Down => Some(Message::NextTag),
+                    Action::Up if lanes => Some(Message::PrevTask),
+                    Action::Up => Some(Message::PrevTag),
+                    Action::OpenTask => Some(Message::OpenTask),
+                    Action::ToggleTag => Some(Message::ToggleTag),
+                    Action::TaskHelp
+                    | Action::NextField
+                    | Action::PrevField
+                    | Action::CloseTask
+                    | Action::SaveTask
+                    | Action::EditDescription
+                    | Action::EditTask
+                    | Action::TogglePreview
+                    | Action::ToggleCalendarWeek
+                    | Action::PrevMonth
+                    | Action::NextMonth => None,
                 }
-                _ => None,
-            },
-            RunningState::TaskView => match key {
-                event::KeyEvent {
-                    code: KeyCode::Tab, ..
-                } => Some(Message::FocusNext),
-                event::KeyEvent {
-                    code: KeyCode::BackTab,
-                    ..
-                } => Some(Message::FocusPrev),
-                event::KeyEvent {
-                    code: KeyCode::Esc, ..
-                } => Some(Message::CloseTask),
-                event::KeyEvent {
-                    code: KeyCode::Char('s'),
-                    modifiers: KeyModifiers::CONTROL,
-                    ..
-                } => Some(Message::SaveTask),
-
-                event => Some(Message::KeyPress(event)),
+            }
+            RunningState::TaskView => match keymap().action(Scope::Task, key) {
+                Some(Action::TaskHelp) => Some(Message::ShowHelp),
+                Some(Action::NextField) => Some(Message::FocusNext),
+                Some(Action::PrevField) => Some(Message::FocusPrev),
+                Some(Action::CloseTask) => Some(Message::CloseTask),
+                Some(Action::SaveTask) => Some(Message::SaveTask),
+                Some(Action::EditDescription) => Some(Message::OpenEditor(EditTarget::Description)),
+                Some(Action::EditTask) => Some(Message::OpenEditor(EditTarget::Task)),
+                Some(Action::TogglePreview) => Some(Message::TogglePreview),
+                _ => Some(Message::KeyPress(key)),
             },
             RunningState::Done => None,
         }
     }
 
     fn update(&mut self, msg: Message) -> Option<Message> {
+        self.model.status = None;
         match msg {
             Message::Quit => {
                 self.model.running_state = RunningState::Done;
             }
             Message::CloseError => self.model.last_error = None,
+            Message::NextPane | Message::PrevPane if !self.model.tags_visible => {}
+            Message::ToggleTable => match self.model.view {
+                View::Table => self.set_view(View::Board),
+                _ => self.set_view(View::Table),
+            },
+            Message::ToggleCalendar => match self.model.view {
+                View::Calendar => self.set_view(View::Board),
+                _ => self.set_view(View::Calendar),
+            },
+            Message::ToggleStats => match self.model.view {
+                View::Stats => self.set_view(View::Board),
+                _ => self.set_view(View::Stats),
+            },
+            Message::ToggleCalendarWeek => self.model.calendar.week ^= true,
+            Message::SelectDay(days) => self.model.calendar.move_days(days),
+            Message::SelectMonth(months) => self.model.calendar.move_months(months),
+            Message::ToggleDayTasks => {
+                let calendar = &mut self.model.calendar;
+                calendar.task = match calendar.task {
+                    Some(_) => None,
+                    None => Some(0),
+                };
+            }
+            Message::SelectDayTask(delta) => {
+                let calendar = &mut self.model.calendar;
+                let tasks = calendar_widget::day_tasks(&self.model.tasks, calendar.day).len();
+                calendar.task = calendar.task.map(|idx| {
+                    idx.saturating_add_signed(delta)
+                        .min(tasks.saturating_sub(1))
+                });
+            }
+            Message::OpenDayTask => {
+                let calendar = &self.model.calendar;
+                let tasks = calendar_widget::day_tasks(&self.model.tasks, calendar.day);
+                let (task, _) = tasks.get(calendar.task.unwrap_or(0))?;
+                return Some(Message::OpenTaskById(task.id?));
+            }
+            Message::SelectSwimlane(row) => self.select_swimlane(row),
+            Message::ToggleSwimlane(row) => {
+                if let Some(row) = self
+                    .model
+                    .swimlanes
+                    .as_mut()
+                    .and_then(|s| s.rows.get_mut(row))
+                {
+                    row.collapsed = !row.collapsed;
+                }
+            }
+            Message::SetSwimlanes(namespace) => {
+                self.model.swimlanes = namespace.map(Swimlanes::new);
+                if let Err(e) = self.update_filtered_tasks() {
+                    self.model.last_error = Some(e);
+                }
+            }
+            Message::SortTable(column) => {
+                self.set_view(View::Table);
+                self.model.table.sort_by(column);
+            }
+            Message::SelectRow(delta) => {
+                let rows = self.model.table.rows(&self.model.tasks);
+                let current = rows.iter().position(|r| *r == self.selected_row());
+                let row = current.map_or(0, |idx| idx.saturating_add_signed(delta));
+                if let Some(&(lane, task)) = rows.get(row.min(rows.len().saturating_sub(1))) {
+                    self.select_task(lane, task);
+                }
+            }
+            Message::ClickRow(row) => {
+                let (lane, task) = self.model.table.rows(&self.model.tasks)[row];
+                return Some(Message::ClickTask(lane, Some(task)));
+            }
+            Message::ToggleTagsPane => {
+                self.model.show_tags = Some(!self.model.tags_visible);
+                self.update_panes();
+            }
+            Message::Resize(width) => {
+                self.model.width = width;
+                self.update_panes();
+            }
             Message::NextPane => match self.model.active_pane {
                 SelectedPane::Lanes => {
                     self.model.active_pane = SelectedPane::Tags;
@@ -344,13 +832,14 @@ impl<'a> App<'a> {
                 if lane_tasks.is_empty() {
                     return None;
                 }
-                self.model.task_view = match self.load_task(lane_tasks[selected_task].id.unwrap()) {
-                    Ok(t) => Some(t.into()),
-                    Err(e) => {
-                        self.model.last_error = Some(e);
-                        return None;
-                    }
-                };
+                self.model.task_view =
+                    match self.db.load_task(lane_tasks[selected_task].id.unwrap()) {
+                        Ok(t) => Some(t.into()),
+                        Err(e) => {
+                            self.model.last_error = Some(e);
+                            return None;
+                        }
+                    };
                 self.model.running_state = RunningState::TaskView;
             }
             Message::NewTask => {
@@ -365,9 +854,25 @@ impl<'a> App<'a> {
                 self.model.running_state = RunningState::MainView;
                 self.model.task_view = None;
             }
+            Message::OpenEditor(target) => self.model.edit_request = Some(target),
+            Message::TogglePreviewPane => {
+                self.model.state.preview = !self.model.state.preview;
+                if let Err(e) = self.model.state.save() {
+                    self.model.last_error = Some(e.context("saving preview pane state"));
+                }
+            }
+            Message::TogglePreview => {
+                if let Some(task_view) = self.model.task_view.as_mut() {
+                    task_view.toggle_preview();
+                }
+            }
             Message::SaveTask => {
+                if let Some(Err(e)) = self.model.task_view.as_ref().map(|tv| tv.validate()) {
+                    self.model.last_error = Some(e);
+                    return None;
+                }
                 let mut task: Task = self.model.task_view.take().unwrap().into();
-                match self.save_task(&mut task).context("saving task") {
+                match self.db.save_task(&mut task).context("saving task") {
                     Ok(t) => t,
                     Err(e) => {
                         self.model.last_error = Some(e);
@@ -382,6 +887,22 @@ impl<'a> App<'a> {
                 }
 
                 self.model.running_state = RunningState::MainView;
+                // pick up fields task view does not keep, like creation time and UUID
+                match self
+                    .db
+                    .load_task(task.id.unwrap())
+                    .context("reloading task")
+                {
+                    Ok(saved) => task = saved,
+                    Err(e) => self.model.last_error = Some(e),
+                }
+                // task may belong to other swimlanes too, so they are regrouped
+                if self.model.swimlanes.is_some() {
+                    if let Err(e) = self.update_filtered_tasks().context("updating tasks view") {
+                        self.model.last_error = Some(e);
+                    }
+                    return None;
+                }
                 let tasks = self.model.tasks.get_mut(&task.state).unwrap();
                 for existing_task in tasks.iter_mut() {
                     if task.id == existing_task.id {
@@ -396,18 +917,19 @@ impl<'a> App<'a> {
                 if to_state == from_state {
                     return None;
                 }
-                let selected_task =
-                    match self.model.lanes[self.model.active_lane].list_state.selected {
-                        Some(v) => v,
-                        None => return None,
-                    };
+                let selected_task = self.model.lanes[self.model.active_lane]
+                    .list_state
+                    .selected?;
 
                 // first, update the db
                 let from_tasks = self.model.tasks.get(&from_state).unwrap();
-                if from_tasks.len() == 0 {
+                if from_tasks.is_empty() {
                     return None;
                 }
-                match self.update_task_state(to_state, from_tasks[selected_task].id.unwrap()) {
+                match self
+                    .db
+                    .update_task_state(to_state, from_tasks[selected_task].id.unwrap())
+                {
                     Ok(_) => {}
                     Err(e) => {
                         self.model.last_error = Some(e);
@@ -426,15 +948,31 @@ impl<'a> App<'a> {
                     }
                     _ => {}
                 }
-                let task = from_tasks.remove(selected_task);
+                let mut task = from_tasks.remove(selected_task);
+                task.state = to_state;
+                // pick up timestamps updated along with state
+                match self
+                    .db
+                    .load_task(task.id.unwrap())
+                    .context("reloading task")
+                {
+                    Ok(saved) => task = saved,
+                    Err(e) => self.model.last_error = Some(e),
+                }
+                // task may be shown in other swimlanes as well
+                for row in self.model.swimlanes.iter_mut().flat_map(|s| &mut s.rows) {
+                    let from_tasks = row.tasks.get_mut(&from_state).unwrap();
+                    if let Some(idx) = from_tasks.iter().position(|t| t.id == task.id) {
+                        from_tasks.remove(idx);
+                        row.tasks.get_mut(&to_state).unwrap().push(task.clone());
+                    }
+                }
                 self.model.tasks.get_mut(&to_state).unwrap().push(task);
             }
             Message::ToggleTaskTag(tag) => {
-                let selected_task =
-                    match self.model.lanes[self.model.active_lane].list_state.selected {
-                        Some(v) => v,
-                        None => return None,
-                    };
+                let selected_task = self.model.lanes[self.model.active_lane]
+                    .list_state
+                    .selected?;
                 let mut task = {
                     let task = self
                         .model
@@ -452,7 +990,7 @@ impl<'a> App<'a> {
                     }
                     task.clone()
                 };
-                if let Err(e) = self.save_task(&mut task).context("saving tags") {
+                if let Err(e) = self.db.save_task(&mut task).context("saving tags") {
                     self.model.last_error = Some(e);
                     return None;
                 }
@@ -465,41 +1003,345 @@ impl<'a> App<'a> {
                     return None;
                 }
             }
-            Message::FocusNext => match self.model.running_state {
-                RunningState::TaskView => {
-                    if let Some(tv) = self.model.task_view.as_mut() {
-                        tv.next_field();
+            Message::NextTheme => color_scheme::next_theme(),
+            Message::ShowHelp => self.model.help = Some(HelpState { scroll: 0 }),
+            Message::ScrollHelp(lines) => {
+                if let Some(help) = self.model.help.as_mut() {
+                    help.scroll = help.scroll.saturating_add_signed(lines);
+                }
+            }
+            Message::CloseHelp => self.model.help = None,
+            Message::ClickTask(lane, None) => {
+                let selected = self.model.lanes[lane].list_state.selected.unwrap_or(0);
+                self.select_task(lane, selected);
+            }
+            Message::ClickTask(lane, Some(task)) => {
+                self.select_task(lane, task);
+                let now = Instant::now();
+                let double = self.model.last_click.is_some_and(|(at, l, t)| {
+                    (l, t) == (lane, task) && now.duration_since(at) < DOUBLE_CLICK_INTERVAL
+                });
+                if double {
+                    self.model.last_click = None;
+                    return Some(Message::OpenTask);
+                }
+                self.model.last_click = Some((now, lane, task));
+                self.model.drag = Some(lane);
+            }
+            Message::ClickTag(tag) => {
+                self.model.active_pane = SelectedPane::Tags;
+                self.model.tags.list_state.select(Some(tag));
+                return Some(Message::ToggleTag);
+            }
+            Message::DropTask(lane) => {
+                let from = self.model.drag.take()?;
+                if let Some(to) = lane
+                    && to != from
+                {
+                    return Some(Message::MoveTask(TaskState::from(to as i32)));
+                }
+            }
+            Message::ScrollLane(lane, delta) => {
+                let lane_state = &mut self.model.lanes[lane];
+                let selected = lane_state.list_state.selected.unwrap_or(0);
+                let task = selected.saturating_add_signed(delta);
+                let tasks = self.model.tasks[&TaskState::from(lane as i32)].len();
+                self.select_task(lane, task.min(tasks.saturating_sub(1)));
+            }
+            Message::OpenCommandLine => self.model.command_line = Some(CommandLine::default()),
+            Message::CloseCommandLine => self.model.command_line = None,
+            Message::CompleteCommand => {
+                let mut cmd = self.model.command_line.take()?;
+                cmd.complete(|arg| self.completions(arg));
+                self.model.command_line = Some(cmd);
+            }
+            Message::RunCommand => {
+                let cmd = self.model.command_line.take()?;
+                match cmd.parse() {
+                    Ok(msg) => return msg,
+                    Err(e) => self.model.last_error = Some(e),
+                }
+            }
+            Message::OpenTaskById(id) => {
+                match self
+                    .db
+                    .load_task(id)
+                    .with_context(|| format!("loading task #{id}"))
+                {
+                    Ok(task) => {
+                        self.model.task_view = Some(task.into());
+                        self.model.running_state = RunningState::TaskView;
                     }
+                    Err(e) => self.model.last_error = Some(e),
                 }
-                _ => {}
-            },
-            Message::FocusPrev => match self.model.running_state {
-                RunningState::TaskView => {
-                    if let Some(tv) = self.model.task_view.as_mut() {
-                        tv.prev_field();
+            }
+            Message::SetFilters(tags) => {
+                for (tag, selected, _) in &mut self.model.tags.items {
+                    *selected = tags.contains(tag);
+                }
+                if let Err(e) = self.update_filtered_tasks() {
+                    self.model.last_error = Some(e);
+                }
+            }
+            Message::RenameTag(from, to) => {
+                let count = match self.db.rename_tag(&from, &to).context("renaming tag") {
+                    Ok(count) => count,
+                    Err(e) => {
+                        self.model.last_error = Some(e);
+                        return None;
+                    }
+                };
+                // keep filter on renamed tag
+                for (tag, _, _) in &mut self.model.tags.items {
+                    if *tag == from {
+                        tag.clone_from(&to);
                     }
                 }
-                _ => {}
-            },
-            Message::KeyPress(event) => match self.model.running_state {
-                RunningState::TaskView => {
-                    if let Some(tv) = self.model.task_view.as_mut() {
-                        tv.process_event(event);
+                if let Err(e) = self
+                    .update_tags()
+                    .and_then(|_| self.update_filtered_tasks())
+                {
+                    self.model.last_error = Some(e);
+                    return None;
+                }
+                self.model.status = Some(format!("Renamed tag {from:?} on {count} tasks"));
+            }
+            Message::Export(format, file) => {
+                match interop::export(&self.db_path, format, Some(&file)) {
+                    Ok(()) => self.model.status = Some(format!("Exported tasks to {file}")),
+                    Err(e) => self.model.last_error = Some(e),
+                }
+            }
+            Message::SwitchBoard(path) => {
+                let path = expand_home(&path);
+                let db = match Db::open(&path) {
+                    Ok(db) => db,
+                    Err(e) => {
+                        self.model.last_error = Some(e);
+                        return None;
                     }
+                };
+                self.db = db;
+                self.model.db_path = display_path(&path);
+                self.db_path = path;
+                self.model.tags.items.clear();
+                if let Err(e) = self
+                    .update_tags()
+                    .and_then(|_| self.update_filtered_tasks())
+                {
+                    self.model.last_error = Some(e);
                 }
-                _ => {}
-            },
+            }
+            Message::FocusNext => {
+                if self.model.running_state == RunningState::TaskView
+                    && let Some(tv) = self.model.task_view.as_mut()
+                {
+                    tv.next_field();
+                }
+            }
+            Message::FocusPrev => {
+                if self.model.running_state == RunningState::TaskView
+                    && let Some(tv) = self.model.task_view.as_mut()
+                {
+                    tv.prev_field();
+                }
+            }
+            Message::KeyPress(event) if let Some(cmd) = self.model.command_line.as_mut() => {
+                cmd.candidates.clear();
+                match event.code {
+                    KeyCode::Backspace if cmd.input.is_empty() => self.model.command_line = None,
+                    KeyCode::Backspace => {
+                        cmd.input.pop();
+                    }
+                    KeyCode::Char(c) => cmd.input.push(c),
+                    _ => {}
+                }
+            }
+            Message::KeyPress(event) => {
+                if self.model.running_state == RunningState::TaskView
+                    && let Some(tv) = self.model.task_view.as_mut()
+                {
+                    tv.process_event(event);
+                }
+            }
         };
         None
     }
 
+    // calendar_key maps keys of calendar view, where moving between lanes and items moves
+    // between days
+    fn calendar_key(&self, key: event::KeyEvent) -> Option<Message> {
+        let day_tasks = self.model.calendar.task.is_some();
+        match keymap().action(Scope::Main, key)? {
+            Action::Help => Some(Message::ShowHelp),
+            Action::Command => Some(Message::OpenCommandLine),
+            Action::Quit => Some(Message::Quit),
+            Action::NewTask => Some(Message::NewTask),
+            Action::NextTheme => Some(Message::NextTheme),
+            Action::ToggleTable => Some(Message::ToggleTable),
+            Action::ToggleCalendar => Some(Message::ToggleCalendar),
+            Action::ToggleCalendarWeek => Some(Message::ToggleCalendarWeek),
+            Action::PrevMonth => Some(Message::SelectMonth(-1)),
+            Action::NextMonth => Some(Message::SelectMonth(1)),
+            Action::ToggleStats => Some(Message::ToggleStats),
+            Action::NextPane | Action::PrevPane => Some(Message::ToggleDayTasks),
+            Action::NextLane => Some(Message::SelectDay(1)),
+            Action::PrevLane => Some(Message::SelectDay(-1)),
+            Action::Down if day_tasks => Some(Message::SelectDayTask(1)),
+            Action::Up if day_tasks => Some(Message::SelectDayTask(-1)),
+            Action::Down => Some(Message::SelectDay(7)),
+            Action::Up => Some(Message::SelectDay(-7)),
+            Action::OpenTask => Some(Message::OpenDayTask),
+            _ => None,
+        }
+    }
+
+    // set_view switches the way tasks are shown. Swimlanes split only the board, so tasks are
+    // regrouped when switching to or from it
+    fn set_view(&mut self, view: View) {
+        let regroup = self.model.swimlanes.is_some()
+            && (view == View::Board) != (self.model.view == View::Board);
+        self.model.view = view;
+        let result = if regroup {
+            self.update_filtered_tasks()
+        } else if view == View::Stats {
+            self.update_stats()
+        } else {
+            Ok(())
+        };
+        if let Err(e) = result {
+            self.model.last_error = Some(e);
+        }
+        self.update_panes();
+    }
+
+    // update_panes decides whether tags pane is shown, moving focus away from it when it is not
+    fn update_panes(&mut self) {
+        // tags pane is hidden on narrow terminals, unless shown explicitly
+        self.model.tags_visible = self
+            .model
+            .show_tags
+            .unwrap_or(self.model.width >= config().layout.hide_tags_width);
+        if !self.model.tags_visible || matches!(self.model.view, View::Calendar | View::Stats) {
+            self.model.active_pane = SelectedPane::Lanes;
+            // hidden pane must not catch mouse clicks
+            self.model.tags.area = Rect::default();
+        }
+    }
+
+    // update_stats computes statistics of tasks shown, i.e. of ones matching tag filters, or of
+    // all but archived ones if no filters are selected
+    fn update_stats(&mut self) -> Result<()> {
+        let history = self.db.history()?;
+        let tasks: HashSet<u64> = self
+            .model
+            .tasks
+            .values()
+            .flatten()
+            .filter_map(|t| t.id)
+            .collect();
+        self.model.stats = Some(Stats::new(&history, &tasks));
+        Ok(())
+    }
+
+    // selected_row returns lane and index of selected task
+    fn selected_row(&self) -> (usize, usize) {
+        let lane = self.model.active_lane;
+        (
+            lane,
+            self.model.lanes[lane].list_state.selected.unwrap_or(0),
+        )
+    }
+
+    // selected_task returns task selected in active lane, if any
+    fn selected_task(&self) -> Option<&Task> {
+        let lane = &self.model.lanes[self.model.active_lane];
+        let state = TaskState::from(self.model.active_lane as i32);
+        self.model.tasks[&state].get(lane.list_state.selected?)
+    }
+
+    // select_task focuses given lane and selects task in it
+    fn select_task(&mut self, lane: usize, task: usize) {
+        self.model.active_pane = SelectedPane::Lanes;
+        self.model.lanes[self.model.active_lane].selected = false;
+        self.model.active_lane = lane;
+        self.model.lanes[lane].selected = true;
+        self.model.lanes[lane].list_state.select(Some(task));
+    }
+
+    // select_swimlane makes given row of swimlanes active, swapping its tasks and lanes into model
+    fn select_swimlane(&mut self, row: usize) {
+        let Some(swimlanes) = self.model.swimlanes.as_mut() else {
+            return;
+        };
+        if row >= swimlanes.rows.len() || row == swimlanes.active {
+            return;
+        }
+        for idx in [swimlanes.active, row] {
+            let row = &mut swimlanes.rows[idx];
+            std::mem::swap(&mut self.model.tasks, &mut row.tasks);
+            std::mem::swap(&mut self.model.lanes, &mut row.lanes);
+        }
+        swimlanes.active = row;
+        for (idx, lane) in self.model.lanes.iter_mut().enumerate() {
+            lane.selected = idx == self.model.active_lane;
+        }
+        self.model.active_pane = SelectedPane::Lanes;
+    }
+
+    // completions lists values that can be given for command argument of given kind
+    fn completions(&self, arg: Arg) -> Vec<String> {
+        match arg {
+            Arg::Tag => {
+                let mut tags: Vec<_> = self.model.tags.items.iter().map(|t| t.0.clone()).collect();
+                tags.sort();
+                tags
+            }
+            Arg::TaskId => {
+                let mut ids: Vec<_> = self
+                    .model
+                    .tasks
+                    .values()
+                    .flatten()
+                    .filter_map(|t| t.id)
+                    .collect();
+                ids.sort();
+                ids.iter().map(u64::to_string).collect()
+            }
+            Arg::Lane => TaskState::ALL
+                .into_iter()
+                .map(|s| <&str>::from(s).to_string())
+                .collect(),
+            Arg::Format => Format::EXPORT_NAMES.iter().map(|f| f.to_string()).collect(),
+            Arg::Column => Column::ALL.map(|c| c.name().to_string()).to_vec(),
+            Arg::Namespace => {
+                // every tag prefix ending with "/", and "@" used for people
+                let mut namespaces: Vec<_> = self
+                    .model
+                    .tags
+                    .items
+                    .iter()
+                    .flat_map(|(tag, _, _)| {
+                        let mut prefixes: Vec<_> = tag
+                            .match_indices('/')
+                            .map(|(idx, _)| tag[..=idx].to_string())
+                            .collect();
+                        if tag.starts_with('@') {
+                            prefixes.push("@".to_string());
+                        }
+                        prefixes
+                    })
+                    .collect();
+                namespaces.sort();
+                namespaces.dedup();
+                namespaces
+            }
+            Arg::Text => Vec::new(),
+        }
+    }
+
     fn update_filtered_tasks(&mut self) -> Result<()> {
-        for state in [
-            TaskState::Todo,
-            TaskState::InProgress,
-            TaskState::Blocked,
-            TaskState::Done,
-        ] {
+        for state in TaskState::ALL {
             self.model.tasks.get_mut(&state).unwrap().truncate(0);
         }
 
@@ -511,59 +1353,38 @@ impl<'a> App<'a> {
             .filter(|x| x.1)
             .map(|x| x.0.clone())
             .collect();
-        let placeholders = std::iter::repeat_n("?", tags.len())
-            .collect::<Vec<_>>()
-            .join(",");
-        let sql = if tags.is_empty() {
-            // All tasks except those with "Archive" tag
-            "SELECT id, state, title, description FROM tasks WHERE
-                NOT EXISTS (SELECT 1 FROM tags WHERE tags.task_id = tasks.id AND tags.tag = 'Archive')"
-        } else {
-            &format!(
-                "SELECT id, state, title, description FROM tasks JOIN tags ON tags.task_id = tasks.id WHERE tags.tag IN ({})",
-                placeholders
-            )
-        };
-        let tx = self.db.transaction().context("loading tasks from DB")?;
-        {
-            let mut stmt = tx.prepare(sql)?;
-            let rows = stmt
-                .query_map(params_from_iter(tags), |r| {
-                    Ok(Task {
-                        id: Some(r.get::<usize, i64>(0)? as u64),
-                        state: r.get::<usize, i32>(1)?.into(),
-                        title: r.get(2)?,
-                        description: r.get(3)?,
-                        tags: Vec::new(),
-                    })
-                })
-                .context("reading tasks from DB")?;
-            for row in rows {
-                let mut task = row.context("decoding task")?;
-                let mut stmt =
-                    tx.prepare("SELECT tag FROM tags WHERE task_id = ? ORDER BY tag DESC")?;
-                for tag_row in stmt.query_map([task.id.unwrap() as i64], |r| r.get(0))? {
-                    task.tags.push(tag_row?);
+        let tasks = self
+            .db
+            .filtered_tasks(&tags)
+            .context("loading tasks from DB")?;
+        match self.model.swimlanes.as_mut() {
+            Some(swimlanes) if self.model.view == View::Board => {
+                swimlanes.group(tasks);
+                let row = &mut swimlanes.rows[swimlanes.active];
+                std::mem::swap(&mut self.model.tasks, &mut row.tasks);
+                std::mem::swap(&mut self.model.lanes, &mut row.lanes);
+            }
+            _ => {
+                for task in tasks {
+                    self.model.tasks.get_mut(&task.state).unwrap().push(task);
                 }
-                self.model.tasks.get_mut(&task.state).unwrap().push(task);
             }
         }
-        tx.commit()?;
 
         // reset focus in task lists
-        for lane in &mut self.model.lanes {
+        for (idx, lane) in self.model.lanes.iter_mut().enumerate() {
             lane.list_state.selected = Some(0);
+            lane.selected = idx == self.model.active_lane;
         }
 
+        if self.model.view == View::Stats {
+            self.update_stats().context("computing statistics")?;
+        }
         Ok(())
     }
 
     fn update_tags(&mut self) -> Result<()> {
-        let mut stmt = self
-            .db
-            .prepare("SELECT DISTINCT tag FROM tags ORDER BY tag DESC")
-            .context("loading tags")?;
-        let rows = stmt.query_map([], |r| r.get(0)).context("querying tags")?;
+        let tags = self.db.tags()?;
 
         let mut selected_tags = HashSet::new();
         for (tag, selected, _) in &self.model.tags.items {
@@ -572,15 +1393,18 @@ impl<'a> App<'a> {
             }
         }
         self.model.tags.items.truncate(0);
-        for row in rows {
-            let tag = row?;
+        for tag in tags {
             let selected = selected_tags.contains(&tag);
-            let clean_mark = if tag == ARCHIVE_TAG { "-" } else { " " };
+            let clean_mark = if tag == config().archive_tag {
+                "-"
+            } else {
+                " "
+            };
             self.model.tags.items.push((tag, selected, clean_mark));
         }
         let item_count = self.model.tags.items.len();
         match self.model.tags.list_state.selected {
-            Some(idx) if idx >= item_count - 1 => {
+            Some(idx) if idx + 1 >= item_count => {
                 self.model
                     .tags
                     .list_state
@@ -591,92 +1415,4 @@ impl<'a> App<'a> {
 
         Ok(())
     }
-
-    fn load_task(&self, id: u64) -> Result<Task> {
-        let mut stmt = self
-            .db
-            .prepare("SELECT state, title, description FROM tasks WHERE id = ?")?;
-        let mut task = stmt.query_row([id as i64], |r| {
-            Ok(Task {
-                id: Some(id),
-                state: r.get::<usize, i32>(0)?.into(),
-                title: r.get(1)?,
-                description: r.get(2)?,
-                tags: Vec::new(),
-            })
-        })?;
-        stmt = self
-            .db
-            .prepare("SELECT tag FROM tags WHERE task_id = ? ORDER BY tag DESC")?;
-        for row in stmt.query_map([id as i64], |r| r.get(0))? {
-            task.tags.push(row?);
-        }
-        Ok(task)
-    }
-
-    fn update_task_state(&self, state: TaskState, id: u64) -> Result<()> {
-        self.db
-            .execute(
-                "UPDATE tasks SET state = ? WHERE id = ?",
-                params![state as i32, id as i64],
-            )
-            .context("updating task state")?;
-        Ok(())
-    }
-
-    // save_task persists task in DB. Sets task.id if needed
-    fn save_task(&mut self, task: &mut Task) -> Result<()> {
-        let (sql, params) = match task.id {
-            Some(id) => (
-                "UPDATE tasks SET state=?,title=?,description=? WHERE id=?",
-                params![task.state as i32, task.title, task.description, id as i64],
-            ),
-            None => (
-                "INSERT INTO tasks (state, title, description) VALUES (?, ?, ?)",
-                params![task.state as i32, task.title, task.description],
-            ),
-        };
-
-        let tx = self.db.transaction().context("starting transaction")?;
-        let id;
-        {
-            tx.execute(sql, params).context("saving task")?;
-            id = match task.id {
-                Some(id) => id,
-                None => tx.last_insert_rowid() as u64,
-            };
-
-            let mut stmt = tx
-                .prepare("SELECT tag FROM tags WHERE task_id=?")
-                .context("querying tags")?;
-            let mut old_tags: HashSet<String> = HashSet::new();
-            for row in stmt
-                .query_map([id as i64], |r| r.get(0))
-                .context("querying tags")?
-            {
-                old_tags.insert(row?);
-            }
-            let new_tags = HashSet::from_iter(task.tags.iter().cloned());
-            let tags_to_remove = old_tags.difference(&new_tags);
-            let tags_to_add = new_tags.difference(&old_tags);
-            let mut stmt = tx
-                .prepare("INSERT INTO tags (tag, task_id) VALUES (?, ?)")
-                .context("inserting new task tags")?;
-            for tag in tags_to_add {
-                stmt.execute(params![tag, id as i64])
-                    .context("inserting new tags")?;
-            }
-            let mut stmt = tx
-                .prepare("DELETE FROM tags WHERE tag = ? AND task_id = ?")
-                .context("removing task old tags")?;
-            for tag in tags_to_remove {
-                stmt.execute(params![tag, id as i64])
-                    .context("removing task old tags")?;
-            }
-        }
-        tx.commit()?;
-
-        task.id = Some(id);
-        Ok(())
-    }
 }
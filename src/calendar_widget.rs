@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, Days, Local, Months, NaiveDate};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph, StatefulWidget, Widget},
+};
+
+use crate::{
+    color_scheme::colors,
+    model::{Task, TaskState},
+    util::truncate,
+};
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+// Mark tells why task is shown on a day
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Mark {
+    Due,
+    Completed,
+}
+
+impl Mark {
+    fn symbol(self) -> &'static str {
+        match self {
+            Mark::Due => "•",
+            Mark::Completed => "✓",
+        }
+    }
+
+    fn style(self, task: &Task, today: NaiveDate) -> Style {
+        let colors = colors();
+        match self {
+            Mark::Completed => colors.border(),
+            Mark::Due if task.state != TaskState::Done && task.due < Some(today) => colors.error(),
+            Mark::Due => colors.tag(),
+        }
+    }
+}
+
+pub(crate) struct CalendarState {
+    pub(crate) day: NaiveDate,
+    // show only week of selected day instead of whole month
+    pub(crate) week: bool,
+    // selected task of the day, when list of day tasks is focused
+    pub(crate) task: Option<usize>,
+}
+
+impl CalendarState {
+    pub(crate) fn new() -> Self {
+        Self {
+            day: Local::now().date_naive(),
+            week: false,
+            task: None,
+        }
+    }
+
+    pub(crate) fn move_days(&mut self, days: i64) {
+        let delta = Days::new(days.unsigned_abs());
+        let day = if days < 0 {
+            self.day.checked_sub_days(delta)
+        } else {
+            self.day.checked_add_days(delta)
+        };
+        self.day = day.unwrap_or(self.day);
+        self.task = self.task.map(|_| 0);
+    }
+
+    // move_months keeps day of month if possible, otherwise moves to the last day of month
+    pub(crate) fn move_months(&mut self, months: i32) {
+        let delta = Months::new(months.unsigned_abs());
+        let day = if months < 0 {
+            self.day.checked_sub_months(delta)
+        } else {
+            self.day.checked_add_months(delta)
+        };
+        self.day = day.unwrap_or(self.day);
+        self.task = self.task.map(|_| 0);
+    }
+
+    // first_day returns the first day shown, which is always Monday
+    fn first_day(&self) -> NaiveDate {
+        let day = if self.week {
+            self.day
+        } else {
+            self.day.with_day(1).unwrap()
+        };
+        day - Days::new(day.weekday().num_days_from_monday() as u64)
+    }
+
+    fn weeks(&self) -> usize {
+        if self.week {
+            return 1;
+        }
+        let first = self.day.with_day(1).unwrap();
+        let last = first + Months::new(1) - Days::new(1);
+        (first.weekday().num_days_from_monday() as usize + last.day() as usize).div_ceil(7)
+    }
+}
+
+// day_tasks returns tasks due or completed on given day, due ones first
+pub(crate) fn day_tasks(
+    tasks: &HashMap<TaskState, Vec<Task>>,
+    day: NaiveDate,
+) -> Vec<(&Task, Mark)> {
+    let mut found = Vec::new();
+    for task in TaskState::ALL.iter().flat_map(|s| &tasks[s]) {
+        if task.due == Some(day) {
+            found.push((task, Mark::Due));
+        }
+        if task
+            .completed_at
+            .is_some_and(|t| t.with_timezone(&Local).date_naive() == day)
+        {
+            found.push((task, Mark::Completed));
+        }
+    }
+    found.sort_by_key(|(task, mark)| (*mark, task.id));
+    found
+}
+
+// Calendar shows tasks on days they are due or were completed, with tasks of selected day
+// listed next to it
+pub(crate) struct Calendar<'a> {
+    pub(crate) tasks: &'a HashMap<TaskState, Vec<Task>>,
+}
+
+impl StatefulWidget for &Calendar<'_> {
+    type State = CalendarState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let colors = colors();
+        let [grid_area, list_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Percentage(30)]).areas(area);
+
+        let title = if state.week {
+            format!(
+                "Week {}, {}",
+                state.day.iso_week().week(),
+                state.day.iso_week().year()
+            )
+        } else {
+            state.day.format("%B %Y").to_string()
+        };
+        let block = pane_block(title, state.task.is_none());
+        let grid = block.inner(grid_area);
+        block.render(grid_area, buf);
+
+        let [header, rows_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(grid);
+        let columns = Layout::horizontal([Constraint::Ratio(1, 7); 7]).spacing(1);
+        for (name, area) in WEEKDAYS.iter().zip(columns.split(header).iter()) {
+            Line::styled(*name, colors.lane_title().bold()).render(*area, buf);
+        }
+        let weeks = state.weeks();
+        let rows =
+            Layout::vertical(vec![Constraint::Ratio(1, weeks as u32); weeks]).split(rows_area);
+        let today = Local::now().date_naive();
+        let mut day = state.first_day();
+        for row in rows.iter() {
+            for cell in columns.split(*row).iter() {
+                self.render_day(day, *cell, buf, state, today);
+                day = day + Days::new(1);
+            }
+        }
+
+        self.render_day_tasks(list_area, buf, state, today);
+    }
+}
+
+impl Calendar<'_> {
+    fn render_day(
+        &self,
+        day: NaiveDate,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &CalendarState,
+        today: NaiveDate,
+    ) {
+        let colors = colors();
+        let mut title_style = colors.lane_title();
+        if !state.week && day.month() != state.day.month() {
+            title_style = colors.border();
+        }
+        if day == today {
+            title_style = title_style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+        }
+        let mut border_style = colors.border();
+        if day == state.day {
+            title_style = colors.cursor();
+            border_style = colors.active_border();
+        }
+        let title = if state.week || day.day() == 1 || day == state.first_day() {
+            day.format("%-d %b").to_string()
+        } else {
+            day.day().to_string()
+        };
+        let block = Block::new()
+            .borders(Borders::TOP)
+            .border_style(border_style)
+            .title(Span::styled(title, title_style));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let tasks = day_tasks(self.tasks, day);
+        let height = inner.height as usize;
+        let shown = if tasks.len() > height {
+            height.saturating_sub(1)
+        } else {
+            tasks.len()
+        };
+        let width = (inner.width as usize).saturating_sub(2);
+        let mut lines: Vec<_> = tasks[..shown]
+            .iter()
+            .map(|(task, mark)| {
+                Line::from(vec![
+                    Span::styled(format!("{} ", mark.symbol()), mark.style(task, today)),
+                    Span::styled(truncate(&task.title, width), colors.text()),
+                ])
+            })
+            .collect();
+        if shown < tasks.len() {
+            lines.push(Line::styled(
+                format!("+{} more", tasks.len() - shown),
+                colors.border(),
+            ));
+        }
+        Paragraph::new(lines).render(inner, buf);
+    }
+
+    fn render_day_tasks(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut CalendarState,
+        today: NaiveDate,
+    ) {
+        let colors = colors();
+        let block = pane_block(
+            state.day.format("%a, %-d %b %Y").to_string(),
+            state.task.is_some(),
+        );
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let tasks = day_tasks(self.tasks, state.day);
+        if tasks.is_empty() {
+            state.task = state.task.map(|_| 0);
+            Line::styled("No tasks", colors.border())
+                .italic()
+                .render(inner, buf);
+            return;
+        }
+        if let Some(selected) = state.task.as_mut() {
+            *selected = (*selected).min(tasks.len() - 1);
+        }
+        // keep selected task visible
+        let height = inner.height as usize;
+        let skip = state.task.map_or(0, |idx| (idx + 1).saturating_sub(height));
+        let width = (inner.width as usize).saturating_sub(2);
+        let lines: Vec<_> = tasks
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .map(|(idx, (task, mark))| {
+                let id = task.id.map(|id| format!("#{id} ")).unwrap_or_default();
+                let line = Line::from(vec![
+                    Span::styled(format!("{} ", mark.symbol()), mark.style(task, today)),
+                    Span::raw(truncate(&format!("{id}{}", task.title), width)),
+                ]);
+                if state.task == Some(idx) {
+                    line.style(colors.cursor())
+                } else {
+                    line.style(colors.text())
+                }
+            })
+            .collect();
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
+
+fn pane_block(title: String, active: bool) -> Block<'static> {
+    let colors = colors();
+    let block = Block::bordered()
+        .title(title)
+        .title_alignment(ratatui::layout::Alignment::Center)
+        .title_style(colors.lane_title())
+        .border_style(colors.border());
+    if active {
+        block
+            .border_type(BorderType::Double)
+            .border_style(colors.active_border())
+            .title_style(colors.lane_active_title().add_modifier(Modifier::BOLD))
+    } else {
+        block
+    }
+}
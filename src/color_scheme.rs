@@ -1,10 +1,61 @@
-use std::sync::LazyLock;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    str::FromStr,
+    sync::{LazyLock, RwLock},
+    time::Duration,
+};
 
-use ratatui::style::Color;
+use anyhow::{Context, Result, anyhow};
+use ratatui::style::{Color, Style, Stylize};
 
-pub(crate) static COLOR_SCHEME: LazyLock<ColorScheme, fn() -> ColorScheme> =
-    LazyLock::new(ColorScheme::load);
+use crate::{
+    config::config,
+    model::TaskState,
+    terminal::{self, Background, ColorDepth},
+};
 
+// Themes shipped with awdy, can be overridden by user theme files with the same name
+const BUNDLED_THEMES: [(&str, &str); 4] = [
+    ("light", include_str!("themes/light.toml")),
+    ("dark", include_str!("themes/dark.toml")),
+    ("solarized", include_str!("themes/solarized.toml")),
+    ("high-contrast", include_str!("themes/high-contrast.toml")),
+];
+
+// Setting value for theme picked by terminal background and for detected color depth
+const AUTO: &str = "auto";
+// How long to wait for terminal to report its background color
+const BACKGROUND_QUERY_TIMEOUT: Duration = Duration::from_millis(200);
+
+static THEMES: LazyLock<RwLock<Themes>> = LazyLock::new(|| {
+    RwLock::new(Themes {
+        themes: vec![Theme {
+            name: "light".to_string(),
+            scheme: ColorScheme::default(),
+        }],
+        current: 0,
+    })
+});
+
+// colors returns color scheme of currently selected theme
+pub(crate) fn colors() -> ColorScheme {
+    let themes = THEMES.read().unwrap();
+    themes.themes[themes.current].scheme
+}
+
+struct Themes {
+    themes: Vec<Theme>,
+    current: usize,
+}
+
+struct Theme {
+    name: String,
+    scheme: ColorScheme,
+}
+
+#[derive(Clone, Copy)]
 pub(crate) struct ColorScheme {
     pub(crate) text_fg: Color,
     pub(crate) text_bg: Color,
@@ -16,10 +67,22 @@ pub(crate) struct ColorScheme {
     pub(crate) lane_active_title_bg: Color,
     pub(crate) status_bar_bg: Color,
     pub(crate) status_bar_fg: Color,
+    pub(crate) border_fg: Color,
+    pub(crate) active_border_fg: Color,
+    pub(crate) tag_fg: Color,
+    pub(crate) error_fg: Color,
+    pub(crate) error_bg: Color,
+    // lane colors, used in charts
+    pub(crate) todo_fg: Color,
+    pub(crate) in_progress_fg: Color,
+    pub(crate) blocked_fg: Color,
+    pub(crate) done_fg: Color,
+    // highlighting is done by reversing text instead of colors
+    monochrome: bool,
 }
 
-impl ColorScheme {
-    fn load() -> Self {
+impl Default for ColorScheme {
+    fn default() -> Self {
         Self {
             text_fg: Color::Reset,
             text_bg: Color::Reset,
@@ -31,6 +94,288 @@ impl ColorScheme {
             lane_active_title_bg: Color::Rgb(0xbf, 0xdb, 0xfe),
             status_bar_fg: Color::Rgb(0, 0, 0),
             status_bar_bg: Color::Rgb(0xd0, 0xd0, 0xd0),
+            border_fg: Color::Reset,
+            active_border_fg: Color::Reset,
+            tag_fg: Color::Reset,
+            error_fg: Color::Black,
+            error_bg: Color::Blue,
+            todo_fg: Color::Rgb(0x25, 0x63, 0xeb),
+            in_progress_fg: Color::Rgb(0xd9, 0x77, 0x06),
+            blocked_fg: Color::Rgb(0xdc, 0x26, 0x26),
+            done_fg: Color::Rgb(0x16, 0xa3, 0x4a),
+            monochrome: false,
+        }
+    }
+}
+
+impl ColorScheme {
+    // set overrides scheme entry by its name with color given as name ("blue"), index ("33") or hex ("#bfdbfe")
+    pub(crate) fn set(&mut self, name: &str, color: &str) -> Result<()> {
+        let entry = match name {
+            "text_fg" => &mut self.text_fg,
+            "text_bg" => &mut self.text_bg,
+            "cursor_fg" => &mut self.cursor_fg,
+            "cursor_bg" => &mut self.cursor_bg,
+            "lane_title_fg" => &mut self.lane_title_fg,
+            "lane_title_bg" => &mut self.lane_title_bg,
+            "lane_active_title_fg" => &mut self.lane_active_title_fg,
+            "lane_active_title_bg" => &mut self.lane_active_title_bg,
+            "status_bar_fg" => &mut self.status_bar_fg,
+            "status_bar_bg" => &mut self.status_bar_bg,
+            "border_fg" => &mut self.border_fg,
+            "active_border_fg" => &mut self.active_border_fg,
+            "tag_fg" => &mut self.tag_fg,
+            "error_fg" => &mut self.error_fg,
+            "error_bg" => &mut self.error_bg,
+            "todo_fg" => &mut self.todo_fg,
+            "in_progress_fg" => &mut self.in_progress_fg,
+            "blocked_fg" => &mut self.blocked_fg,
+            "done_fg" => &mut self.done_fg,
+            _ => return Err(anyhow!("unknown color scheme entry")),
+        };
+        *entry = Color::from_str(color).map_err(|_| anyhow!("invalid color {color:?}"))?;
+        Ok(())
+    }
+
+    pub(crate) fn text(&self) -> Style {
+        Style::new().fg(self.text_fg).bg(self.text_bg)
+    }
+
+    pub(crate) fn cursor(&self) -> Style {
+        self.highlight(self.cursor_fg, self.cursor_bg)
+    }
+
+    pub(crate) fn lane_title(&self) -> Style {
+        Style::new().fg(self.lane_title_fg).bg(self.lane_title_bg)
+    }
+
+    pub(crate) fn lane_active_title(&self) -> Style {
+        self.highlight(self.lane_active_title_fg, self.lane_active_title_bg)
+    }
+
+    pub(crate) fn status_bar(&self) -> Style {
+        self.highlight(self.status_bar_fg, self.status_bar_bg)
+    }
+
+    pub(crate) fn border(&self) -> Style {
+        Style::new().fg(self.border_fg).bg(self.text_bg)
+    }
+
+    pub(crate) fn active_border(&self) -> Style {
+        Style::new().fg(self.active_border_fg).bg(self.text_bg)
+    }
+
+    pub(crate) fn tag(&self) -> Style {
+        self.text().fg(self.tag_fg)
+    }
+
+    pub(crate) fn error(&self) -> Style {
+        self.highlight(self.error_fg, self.error_bg)
+    }
+
+    pub(crate) fn lane(&self, state: TaskState) -> Style {
+        let fg = match state {
+            TaskState::Todo => self.todo_fg,
+            TaskState::InProgress => self.in_progress_fg,
+            TaskState::Blocked => self.blocked_fg,
+            TaskState::Done => self.done_fg,
+        };
+        self.text().fg(fg)
+    }
+
+    fn highlight(&self, fg: Color, bg: Color) -> Style {
+        if self.monochrome {
+            Style::new().reversed()
+        } else {
+            Style::new().fg(fg).bg(bg)
+        }
+    }
+
+    // with_depth converts colors to ones terminal is able to show
+    fn with_depth(mut self, depth: ColorDepth) -> Self {
+        for color in [
+            &mut self.text_fg,
+            &mut self.text_bg,
+            &mut self.cursor_fg,
+            &mut self.cursor_bg,
+            &mut self.lane_title_fg,
+            &mut self.lane_title_bg,
+            &mut self.lane_active_title_fg,
+            &mut self.lane_active_title_bg,
+            &mut self.status_bar_fg,
+            &mut self.status_bar_bg,
+            &mut self.border_fg,
+            &mut self.active_border_fg,
+            &mut self.tag_fg,
+            &mut self.error_fg,
+            &mut self.error_bg,
+            &mut self.todo_fg,
+            &mut self.in_progress_fg,
+            &mut self.blocked_fg,
+            &mut self.done_fg,
+        ] {
+            *color = match (depth, *color) {
+                (ColorDepth::TrueColor, c) | (_, c @ Color::Reset) => c,
+                (ColorDepth::Monochrome, _) => Color::Reset,
+                (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => nearest_256([r, g, b]),
+                (ColorDepth::Ansi16, Color::Rgb(r, g, b)) => nearest_16([r, g, b]),
+                (ColorDepth::Ansi16, Color::Indexed(i)) if i >= 16 => nearest_16(indexed_rgb(i)),
+                (_, c) => c,
+            };
+        }
+        self.monochrome = depth == ColorDepth::Monochrome;
+        self
+    }
+
+    fn apply(&mut self, colors: &BTreeMap<String, String>) -> Result<()> {
+        for (name, color) in colors {
+            self.set(name, color).with_context(|| name.clone())?;
+        }
+        Ok(())
+    }
+
+    // parse reads theme file, a TOML table of scheme entries. Entries not given are taken from default theme
+    fn parse(text: &str) -> Result<Self> {
+        let colors: BTreeMap<String, String> = toml::from_str(text)?;
+        let mut scheme = Self::default();
+        scheme.apply(&colors)?;
+        Ok(scheme)
+    }
+}
+
+// themes_dir is directory with user themes ($XDG_CONFIG_HOME/awdy/themes on Linux)
+fn themes_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("awdy").join("themes"))
+}
+
+// init loads bundled and user themes and selects one set in config
+pub(crate) fn init() -> Result<()> {
+    let mut themes = Vec::new();
+    for (name, text) in BUNDLED_THEMES {
+        themes.push(Theme {
+            name: name.to_string(),
+            scheme: ColorScheme::parse(text)
+                .with_context(|| format!("parsing bundled theme {name}"))?,
+        });
+    }
+
+    if let Some(dir) = themes_dir().filter(|dir| dir.is_dir()) {
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(&dir).with_context(|| format!("listing {}", dir.display()))? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|e| e == "toml") {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        for path in paths {
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            let text =
+                fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+            let scheme = ColorScheme::parse(&text)
+                .with_context(|| format!("parsing theme {}", path.display()))?;
+            match themes.iter_mut().find(|t| t.name == name) {
+                Some(theme) => theme.scheme = scheme,
+                None => themes.push(Theme { name, scheme }),
+            }
+        }
+    }
+
+    // overrides from config apply to every theme
+    let depth = match config().color_depth.as_str() {
+        AUTO => terminal::color_depth(),
+        depth => depth.parse()?,
+    };
+    for theme in &mut themes {
+        theme.scheme.apply(&config().colors)?;
+        theme.scheme = theme.scheme.with_depth(depth);
+    }
+
+    // terminals that can't tell their background are assumed to be light, as most of them are by default
+    let name = match config().theme.as_str() {
+        AUTO => match terminal::background(BACKGROUND_QUERY_TIMEOUT) {
+            Some(Background::Dark) => "dark",
+            Some(Background::Light) | None => "light",
+        },
+        name => name,
+    };
+    let current = themes.iter().position(|t| t.name == name).ok_or_else(|| {
+        anyhow!(
+            "unknown theme {name:?}, available themes are: {AUTO}, {}",
+            themes
+                .iter()
+                .map(|t| t.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    })?;
+    *THEMES.write().unwrap() = Themes { themes, current };
+    Ok(())
+}
+
+// next_theme switches to the next available theme
+pub(crate) fn next_theme() {
+    let mut themes = THEMES.write().unwrap();
+    themes.current = (themes.current + 1) % themes.themes.len();
+}
+
+// Standard xterm colors, actual ones depend on terminal settings
+const ANSI_16: [(Color, [u8; 3]); 16] = [
+    (Color::Black, [0, 0, 0]),
+    (Color::Red, [205, 0, 0]),
+    (Color::Green, [0, 205, 0]),
+    (Color::Yellow, [205, 205, 0]),
+    (Color::Blue, [0, 0, 238]),
+    (Color::Magenta, [205, 0, 205]),
+    (Color::Cyan, [0, 205, 205]),
+    (Color::Gray, [229, 229, 229]),
+    (Color::DarkGray, [127, 127, 127]),
+    (Color::LightRed, [255, 0, 0]),
+    (Color::LightGreen, [0, 255, 0]),
+    (Color::LightYellow, [255, 255, 0]),
+    (Color::LightBlue, [92, 92, 255]),
+    (Color::LightMagenta, [255, 0, 255]),
+    (Color::LightCyan, [0, 255, 255]),
+    (Color::White, [255, 255, 255]),
+];
+// Levels of every component in 6x6x6 color cube occupying indices 16-231 of 256-color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn distance(a: [u8; 3], b: [u8; 3]) -> u32 {
+    a.iter()
+        .zip(b)
+        .map(|(&a, b)| (a as i32 - b as i32).pow(2) as u32)
+        .sum()
+}
+
+fn nearest_16(rgb: [u8; 3]) -> Color {
+    ANSI_16
+        .iter()
+        .min_by_key(|(_, c)| distance(rgb, *c))
+        .unwrap()
+        .0
+}
+
+// nearest_256 picks closest color from color cube and grayscale ramp, first 16 colors are skipped
+// as they are often redefined by terminal themes
+fn nearest_256(rgb: [u8; 3]) -> Color {
+    (16..=255)
+        .min_by_key(|&i| distance(rgb, indexed_rgb(i)))
+        .map(Color::Indexed)
+        .unwrap()
+}
+
+fn indexed_rgb(i: u8) -> [u8; 3] {
+    match i {
+        0..16 => ANSI_16[i as usize].1,
+        16..232 => {
+            let i = (i - 16) as usize;
+            [
+                CUBE_LEVELS[i / 36],
+                CUBE_LEVELS[i / 6 % 6],
+                CUBE_LEVELS[i % 6],
+            ]
         }
+        _ => [8 + 10 * (i - 232); 3],
     }
 }
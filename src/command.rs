@@ -0,0 +1,299 @@
+use anyhow::{Result, anyhow};
+
+use crate::{config::config, interop::Format, model::Message};
+
+// Kinds of command arguments, telling how they are completed
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum Arg {
+    Tag,
+    TaskId,
+    Lane,
+    Format,
+    Column,
+    Namespace,
+    Text,
+}
+
+struct Command {
+    name: &'static str,
+    args: &'static [Arg],
+    // last argument can be repeated
+    variadic: bool,
+    usage: &'static str,
+}
+
+const COMMANDS: [Command; 17] = [
+    Command {
+        name: "quit",
+        args: &[],
+        variadic: false,
+        usage: "quit",
+    },
+    Command {
+        name: "new",
+        args: &[],
+        variadic: false,
+        usage: "new",
+    },
+    Command {
+        name: "open",
+        args: &[Arg::TaskId],
+        variadic: false,
+        usage: "open <task id>",
+    },
+    Command {
+        name: "move",
+        args: &[Arg::Lane],
+        variadic: false,
+        usage: "move <lane>",
+    },
+    Command {
+        name: "tag",
+        args: &[Arg::Tag],
+        variadic: false,
+        usage: "tag <tag>",
+    },
+    Command {
+        name: "archive",
+        args: &[],
+        variadic: false,
+        usage: "archive",
+    },
+    Command {
+        name: "filter",
+        args: &[Arg::Tag],
+        variadic: true,
+        usage: "filter [tag]...",
+    },
+    Command {
+        name: "rename-tag",
+        args: &[Arg::Tag, Arg::Text],
+        variadic: false,
+        usage: "rename-tag <tag> <new name>",
+    },
+    Command {
+        name: "export",
+        args: &[Arg::Format, Arg::Text],
+        variadic: false,
+        usage: "export <format> <file>",
+    },
+    Command {
+        name: "board",
+        args: &[Arg::Text],
+        variadic: false,
+        usage: "board <database file>",
+    },
+    Command {
+        name: "table",
+        args: &[],
+        variadic: false,
+        usage: "table",
+    },
+    Command {
+        name: "calendar",
+        args: &[],
+        variadic: false,
+        usage: "calendar",
+    },
+    Command {
+        name: "stats",
+        args: &[],
+        variadic: false,
+        usage: "stats",
+    },
+    Command {
+        name: "sort",
+        args: &[Arg::Column],
+        variadic: false,
+        usage: "sort <column>",
+    },
+    Command {
+        name: "swimlanes",
+        args: &[Arg::Namespace],
+        variadic: true,
+        usage: "swimlanes [tag namespace]",
+    },
+    Command {
+        name: "theme",
+        args: &[],
+        variadic: false,
+        usage: "theme",
+    },
+    Command {
+        name: "help",
+        args: &[],
+        variadic: false,
+        usage: "help",
+    },
+];
+
+// CommandLine is state of vim-like command line opened with ":"
+#[derive(Default)]
+pub(crate) struct CommandLine {
+    pub(crate) input: String,
+    // candidates for word being completed, shown when completion is ambiguous
+    pub(crate) candidates: Vec<String>,
+}
+
+impl CommandLine {
+    // parse turns command line into message. Commands can be abbreviated as long as
+    // abbreviation is not ambiguous
+    pub(crate) fn parse(&self) -> Result<Option<Message>> {
+        let words: Vec<_> = split_words(&self.input).into_iter().map(|w| w.1).collect();
+        let Some((name, args)) = words.split_first() else {
+            return Ok(None);
+        };
+        let command = find_command(name)?;
+        let expected = command.args.len();
+        if args.len() > expected && !command.variadic
+            || args.len() < expected && !(command.variadic && args.len() == expected - 1)
+        {
+            return Err(anyhow!("usage: {}", command.usage));
+        }
+        let arg = |i: usize| args[i].clone();
+        let message = match command.name {
+            "quit" => Message::Quit,
+            "new" => Message::NewTask,
+            "open" => Message::OpenTaskById(
+                args[0]
+                    .trim_start_matches('#')
+                    .parse()
+                    .map_err(|_| anyhow!("invalid task id {:?}", args[0]))?,
+            ),
+            "move" => Message::MoveTask(args[0].parse()?),
+            "tag" => Message::ToggleTaskTag(arg(0)),
+            "archive" => Message::ToggleTaskTag(config().archive_tag.clone()),
+            "filter" => Message::SetFilters(args.to_vec()),
+            "rename-tag" => Message::RenameTag(arg(0), arg(1)),
+            "export" => {
+                let format: Format = args[0].parse()?;
+                if !Format::EXPORT_NAMES.contains(&args[0].as_str()) {
+                    return Err(anyhow!("exporting to {} is not supported", args[0]));
+                }
+                if args[1] == "-" {
+                    return Err(anyhow!("can't export to stdout from here"));
+                }
+                Message::Export(format, arg(1))
+            }
+            "board" => Message::SwitchBoard(arg(0)),
+            "table" => Message::ToggleTable,
+            "calendar" => Message::ToggleCalendar,
+            "stats" => Message::ToggleStats,
+            "sort" => Message::SortTable(args[0].parse()?),
+            "swimlanes" => match args {
+                [] => Message::SetSwimlanes(None),
+                [namespace] => Message::SetSwimlanes(Some(namespace.clone())),
+                _ => return Err(anyhow!("usage: {}", command.usage)),
+            },
+            "theme" => Message::NextTheme,
+            "help" => Message::ShowHelp,
+            _ => unreachable!(),
+        };
+        Ok(Some(message))
+    }
+
+    // complete extends word under cursor (which is always at the end) with the longest
+    // prefix common to all values that fit there. Values of arguments are provided by given function
+    pub(crate) fn complete(&mut self, values: impl Fn(Arg) -> Vec<String>) {
+        let words = split_words(&self.input);
+        let quotes = self.input.matches('"').count();
+        let new_word =
+            quotes.is_multiple_of(2) && self.input.chars().last().is_none_or(char::is_whitespace);
+        let (start, word, idx) = match words.last() {
+            Some((start, word)) if !new_word => (*start, word.clone(), words.len() - 1),
+            _ => (self.input.len(), String::new(), words.len()),
+        };
+
+        let candidates = if idx == 0 {
+            COMMANDS.iter().map(|c| c.name.to_string()).collect()
+        } else {
+            let Ok(command) = find_command(&words[0].1) else {
+                return;
+            };
+            let arg = match command.args.get(idx - 1) {
+                Some(arg) => *arg,
+                None if command.variadic => *command.args.last().unwrap(),
+                None => return,
+            };
+            values(arg)
+        };
+        let lower = word.to_lowercase();
+        let matching: Vec<_> = candidates
+            .into_iter()
+            .filter(|c| c.to_lowercase().starts_with(&lower))
+            .collect();
+
+        self.candidates.clear();
+        match matching.as_slice() {
+            [] => return,
+            [only] => {
+                self.input.truncate(start);
+                self.input.push_str(&quote(only));
+                self.input.push(' ');
+                return;
+            }
+            _ => {}
+        }
+        let mut prefix = matching[0].clone();
+        for m in &matching[1..] {
+            let len = prefix
+                .chars()
+                .zip(m.chars())
+                .take_while(|(a, b)| a.to_lowercase().eq(b.to_lowercase()))
+                .map(|(a, _)| a.len_utf8())
+                .sum();
+            prefix.truncate(len);
+        }
+        if prefix.len() > word.len() {
+            self.input.truncate(start);
+            match quote(&prefix) {
+                // keep quote open, as word is not finished
+                quoted if quoted.starts_with('"') => {
+                    self.input.push_str(&quoted[..quoted.len() - 1])
+                }
+                quoted => self.input.push_str(&quoted),
+            }
+        }
+        self.candidates = matching;
+    }
+}
+
+fn find_command(name: &str) -> Result<&'static Command> {
+    if let Some(command) = COMMANDS.iter().find(|c| c.name == name) {
+        return Ok(command);
+    }
+    let mut matching = COMMANDS.iter().filter(|c| c.name.starts_with(name));
+    match (matching.next(), matching.next()) {
+        (Some(command), None) => Ok(command),
+        (Some(_), Some(_)) => Err(anyhow!("ambiguous command {name:?}")),
+        (None, _) => Err(anyhow!("unknown command {name:?}")),
+    }
+}
+
+// split_words splits command line into words with their offsets. Double quotes group words
+// with spaces in them
+fn split_words(input: &str) -> Vec<(usize, String)> {
+    let mut words = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+    let mut quoted = false;
+    for (idx, c) in input.char_indices() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                current.get_or_insert((idx, String::new()));
+            }
+            c if c.is_whitespace() && !quoted => words.extend(current.take()),
+            c => current.get_or_insert((idx, String::new())).1.push(c),
+        }
+    }
+    words.extend(current);
+    words
+}
+
+fn quote(word: &str) -> String {
+    if word.contains(char::is_whitespace) {
+        format!("\"{word}\"")
+    } else {
+        word.to_string()
+    }
+}
@@ -0,0 +1,199 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+    time::Duration,
+};
+
+use anyhow::{Context, Result, anyhow};
+use serde::Deserialize;
+
+use crate::{color_scheme::ColorScheme, keymap::Keymap, terminal::ColorDepth};
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+// config returns configuration loaded at startup, or the default one if none was loaded
+pub(crate) fn config() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    // database used when not inside of workspace
+    pub db: Option<String>,
+    pub archive_tag: String,
+    // tags selected in tags pane at startup
+    pub default_filters: Vec<String>,
+    pub poll_interval_ms: u64,
+    // capture mouse, which disables selecting text with it in most terminals
+    pub mouse: bool,
+    // tag namespace (prefix like "team/") to split board into swimlanes by, empty for no swimlanes
+    pub swimlanes: String,
+    pub layout: LayoutConfig,
+    // name of bundled or user color theme, "auto" picks light or dark one matching terminal background
+    pub theme: String,
+    // colors supported by terminal: "truecolor", "256", "16" or "none", detected from environment if "auto"
+    pub color_depth: String,
+    // overrides of color scheme entries applied on top of every theme, e.g. cursor_bg = "#bfdbfe"
+    pub colors: BTreeMap<String, String>,
+    // keys bound to actions, replacing default ones, e.g. save_task = ["ctrl+w", "f2"]
+    pub keys: BTreeMap<String, KeyBinding>,
+}
+
+// Keys bound to action, either single key or list of them
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum KeyBinding {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LayoutConfig {
+    // width of tags pane, in percents of terminal width
+    pub tags_width: u16,
+    // relative widths of TODO, In progress, Blocked and Done lanes
+    pub lanes: [u16; 4],
+    // lanes are placed one under another instead of side by side
+    pub stacked: bool,
+    // terminal width below which tags pane is hidden, unless toggled explicitly
+    pub hide_tags_width: u16,
+    // terminal width below which only active lane is shown, with tabs to switch to others
+    pub single_lane_width: u16,
+    // height of task cards in lanes, in lines
+    pub card_height: u16,
+    // minimal terminal width to show Markdown preview of description next to its editor, 0 disables
+    pub split_preview_width: u16,
+    // where preview of selected task is shown in main view
+    pub preview: PreviewPosition,
+    // size of preview pane, in percents of terminal width or height
+    pub preview_size: u16,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PreviewPosition {
+    Right,
+    Bottom,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            db: None,
+            archive_tag: "Archive".to_string(),
+            default_filters: Vec::new(),
+            poll_interval_ms: 250,
+            mouse: true,
+            swimlanes: String::new(),
+            layout: LayoutConfig::default(),
+            theme: "auto".to_string(),
+            color_depth: "auto".to_string(),
+            colors: BTreeMap::new(),
+            keys: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            tags_width: 10,
+            lanes: [1, 1, 1, 1],
+            stacked: false,
+            hide_tags_width: 100,
+            single_lane_width: 80,
+            card_height: 1,
+            split_preview_width: 160,
+            preview: PreviewPosition::Right,
+            preview_size: 35,
+        }
+    }
+}
+
+impl Config {
+    // default_path is config.toml in user config directory ($XDG_CONFIG_HOME/awdy on Linux)
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("awdy").join("config.toml"))
+    }
+
+    // load reads config from given file. If no file given, config is read from default location,
+    // if it exists there
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => match Self::default_path() {
+                Some(path) if path.exists() => path,
+                _ => return Ok(Self::default()),
+            },
+        };
+        let text =
+            fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+        let config: Self =
+            toml::from_str(&text).with_context(|| format!("parsing {}", path.display()))?;
+        config
+            .validate()
+            .with_context(|| format!("invalid config {}", path.display()))?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<()> {
+        let mut errors = Vec::new();
+        if self.archive_tag.trim().is_empty() || self.archive_tag.contains(',') {
+            errors.push("archive_tag must be non-empty and must not contain commas".to_string());
+        }
+        if self.poll_interval_ms == 0 {
+            errors.push("poll_interval_ms must be positive".to_string());
+        }
+        if self.layout.tags_width > 90 {
+            errors.push("layout.tags_width must be between 0 and 90".to_string());
+        }
+        if !(1..=10).contains(&self.layout.card_height) {
+            errors.push("layout.card_height must be between 1 and 10".to_string());
+        }
+        if !(10..=90).contains(&self.layout.preview_size) {
+            errors.push("layout.preview_size must be between 10 and 90".to_string());
+        }
+        if self.theme.trim().is_empty() {
+            errors.push("theme must be non-empty".to_string());
+        }
+        if self.color_depth != "auto"
+            && let Err(e) = self.color_depth.parse::<ColorDepth>()
+        {
+            errors.push(format!(
+                "color_depth: {e}, expected auto, truecolor, 256, 16 or none"
+            ));
+        }
+        if self.layout.lanes.contains(&0) {
+            errors.push("layout.lanes must be positive".to_string());
+        }
+        let mut colors = ColorScheme::default();
+        for (name, color) in &self.colors {
+            if let Err(e) = colors.set(name, color) {
+                errors.push(format!("colors.{name}: {e}"));
+            }
+        }
+        if let Err(e) = Keymap::new(&self.keys) {
+            errors.extend(e.into_iter().map(|e| format!("keys: {e}")));
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!(errors.join("\n")))
+        }
+    }
+
+    // init makes config available to the rest of application, can be called only once
+    pub fn init(self) {
+        if CONFIG.set(self).is_err() {
+            panic!("config is already initialized");
+        }
+    }
+
+    pub(crate) fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms)
+    }
+}
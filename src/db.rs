@@ -0,0 +1,424 @@
+use std::{collections::HashSet, fs, path::Path, time::Duration};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{
+    Connection, OpenFlags, OptionalExtension, Row, backup::Backup, params, params_from_iter,
+};
+use uuid::Uuid;
+
+use crate::{
+    config::config,
+    model::{Task, TaskState, Transition},
+};
+
+// Schema changes applied on top of the initial tables, indexed by PRAGMA user_version
+const MIGRATIONS: &[&str] = &[
+    "ALTER TABLE tasks ADD COLUMN created_at INTEGER;
+    ALTER TABLE tasks ADD COLUMN updated_at INTEGER;",
+    "ALTER TABLE tasks ADD COLUMN completed_at INTEGER;
+    UPDATE tasks SET completed_at = updated_at WHERE state = 3;",
+    "ALTER TABLE tasks ADD COLUMN uuid TEXT;
+    CREATE UNIQUE INDEX tasks_uuid ON tasks (uuid);",
+    "ALTER TABLE tasks ADD COLUMN due TEXT;",
+    // history of existing tasks is rebuilt from their timestamps, as if they were created in
+    // TODO (or lane they are in now) and moved to Done when completed
+    "CREATE TABLE task_history (
+        task_id INTEGER NOT NULL,
+        state INTEGER NOT NULL,
+        at INTEGER NOT NULL
+    );
+    CREATE INDEX task_history_task ON task_history (task_id, at);
+    INSERT INTO task_history (task_id, state, at)
+        SELECT id, CASE WHEN state = 3 THEN 0 ELSE state END, created_at FROM tasks
+        WHERE created_at IS NOT NULL;
+    INSERT INTO task_history (task_id, state, at)
+        SELECT id, state, completed_at FROM tasks WHERE state = 3 AND completed_at IS NOT NULL;",
+];
+
+const TASK_COLUMNS: &str = "tasks.id, tasks.state, tasks.title, tasks.description, \
+    tasks.created_at, tasks.updated_at, tasks.completed_at, tasks.uuid, tasks.due";
+
+// Records task entering lane at given time, unless it is already there according to history.
+// Time older than the latest history entry (like one of imported task) is moved up to it, so
+// the change does not end up in the middle of history. Takes task id, state and time as parameters
+const RECORD_STATE: &str = "INSERT INTO task_history (task_id, state, at)
+    SELECT ?1, ?2, MAX(?3, COALESCE((SELECT MAX(at) FROM task_history WHERE task_id = ?1), ?3))
+    WHERE ?2 IS NOT (SELECT state FROM task_history WHERE task_id = ?1 ORDER BY at DESC, rowid DESC LIMIT 1)";
+
+// completed_at is kept while task stays in Done lane and reset once it leaves it.
+// Takes new state and completion time as parameters
+const COMPLETED_AT: &str =
+    "completed_at = CASE WHEN ? = 3 THEN COALESCE(completed_at, ?) ELSE NULL END";
+
+pub(crate) struct Db {
+    conn: Connection,
+}
+
+impl Db {
+    // open opens database at given path, creating it along with missing parent directories
+    pub(crate) fn open(path: &Path) -> Result<Self> {
+        if let Some(dir) = path.parent()
+            && !dir.as_os_str().is_empty()
+        {
+            fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+        }
+        let conn = Connection::open(path)
+            .with_context(|| format!("opening database {}", path.display()))?;
+        let mut db = Self { conn };
+        db.init().context("initializing database")?;
+        Ok(db)
+    }
+
+    // open_copy loads database at given path into memory and upgrades the copy, so database can
+    // be read without touching the file
+    pub(crate) fn open_copy(path: &Path) -> Result<Self> {
+        let src = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .with_context(|| format!("opening database {}", path.display()))?;
+        let mut conn = Connection::open_in_memory()?;
+        Backup::new(&src, &mut conn)?
+            .run_to_completion(100, Duration::ZERO, None)
+            .with_context(|| format!("reading database {}", path.display()))?;
+        let mut db = Self { conn };
+        db.init().context("initializing database")?;
+        Ok(db)
+    }
+
+    fn init(&mut self) -> Result<()> {
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS tasks (
+                id INTEGER PRIMARY KEY,
+                state INTEGER NOT NULL,
+                title TEXT NOT NULL,
+                description TEXT
+            )",
+            (),
+        )?;
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS tags (
+                tag TEXT NOT NULL,
+                task_id INTEGER NOT NULL,
+                PRIMARY KEY (tag, task_id)
+            )",
+            (),
+        )?;
+
+        let version: i64 = self
+            .conn
+            .query_row("PRAGMA user_version", [], |r| r.get(0))?;
+        let tx = self.conn.transaction()?;
+        for (idx, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+            tx.execute_batch(migration)
+                .with_context(|| format!("applying migration #{}", idx + 1))?;
+        }
+        tx.pragma_update(None, "user_version", MIGRATIONS.len() as i64)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn task_from_row(r: &Row) -> rusqlite::Result<Task> {
+        Ok(Task {
+            id: Some(r.get::<usize, i64>(0)? as u64),
+            state: r.get::<usize, i32>(1)?.into(),
+            title: r.get(2)?,
+            description: r.get(3)?,
+            tags: Vec::new(),
+            created_at: r.get::<usize, Option<i64>>(4)?.and_then(from_timestamp),
+            updated_at: r.get::<usize, Option<i64>>(5)?.and_then(from_timestamp),
+            completed_at: r.get::<usize, Option<i64>>(6)?.and_then(from_timestamp),
+            uuid: r.get(7)?,
+            due: r.get(8)?,
+        })
+    }
+
+    fn load_tags(&self, task: &mut Task) -> Result<()> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT tag FROM tags WHERE task_id = ? ORDER BY tag DESC")?;
+        for row in stmt.query_map([task.id.unwrap() as i64], |r| r.get(0))? {
+            task.tags.push(row?);
+        }
+        Ok(())
+    }
+
+    fn query_tasks<P: rusqlite::Params>(&self, sql: &str, params: P) -> Result<Vec<Task>> {
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt
+            .query_map(params, Self::task_from_row)
+            .context("reading tasks from DB")?;
+        let mut tasks = Vec::new();
+        for row in rows {
+            let mut task = row.context("decoding task")?;
+            self.load_tags(&mut task)?;
+            tasks.push(task);
+        }
+        Ok(tasks)
+    }
+
+    // tasks having any of given tags, or all non-archived tasks if no tags given
+    pub(crate) fn filtered_tasks(&self, tags: &[String]) -> Result<Vec<Task>> {
+        if tags.is_empty() {
+            return self.query_tasks(
+                &format!(
+                    "SELECT {TASK_COLUMNS} FROM tasks WHERE
+                    NOT EXISTS (SELECT 1 FROM tags WHERE tags.task_id = tasks.id AND tags.tag = ?)"
+                ),
+                [&config().archive_tag],
+            );
+        }
+        let placeholders = std::iter::repeat_n("?", tags.len())
+            .collect::<Vec<_>>()
+            .join(",");
+        self.query_tasks(
+            &format!(
+                "SELECT {TASK_COLUMNS} FROM tasks JOIN tags ON tags.task_id = tasks.id WHERE tags.tag IN ({placeholders})"
+            ),
+            params_from_iter(tags),
+        )
+    }
+
+    pub(crate) fn all_tasks(&self) -> Result<Vec<Task>> {
+        self.query_tasks(&format!("SELECT {TASK_COLUMNS} FROM tasks ORDER BY id"), [])
+    }
+
+    pub(crate) fn load_task(&self, id: u64) -> Result<Task> {
+        let mut task = self.conn.query_row(
+            &format!("SELECT {TASK_COLUMNS} FROM tasks WHERE id = ?"),
+            [id as i64],
+            Self::task_from_row,
+        )?;
+        self.load_tags(&mut task)?;
+        Ok(task)
+    }
+
+    pub(crate) fn tags(&self) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT DISTINCT tag FROM tags ORDER BY tag DESC")
+            .context("loading tags")?;
+        let rows = stmt.query_map([], |r| r.get(0)).context("querying tags")?;
+        let mut tags = Vec::new();
+        for row in rows {
+            tags.push(row?);
+        }
+        Ok(tags)
+    }
+
+    // rename_tag replaces tag on all tasks having it, stamping their modification time.
+    // Returns number of renamed tasks
+    pub(crate) fn rename_tag(&mut self, from: &str, to: &str) -> Result<usize> {
+        let tx = self.conn.transaction()?;
+        let count = tx.execute(
+            "UPDATE tasks SET updated_at = ? WHERE id IN (SELECT task_id FROM tags WHERE tag = ?)",
+            params![Utc::now().timestamp(), from],
+        )?;
+        // tasks that already have new tag just lose the old one
+        tx.execute(
+            "UPDATE OR IGNORE tags SET tag = ? WHERE tag = ?",
+            params![to, from],
+        )?;
+        tx.execute("DELETE FROM tags WHERE tag = ?", [from])?;
+        tx.commit()?;
+        Ok(count)
+    }
+
+    pub(crate) fn update_task_state(&mut self, state: TaskState, id: u64) -> Result<()> {
+        let now = Utc::now().timestamp();
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            &format!("UPDATE tasks SET state = ?, updated_at = ?, {COMPLETED_AT} WHERE id = ?"),
+            params![state as i32, now, state as i32, now, id as i64],
+        )
+        .context("updating task state")?;
+        tx.execute(RECORD_STATE, params![id as i64, state as i32, now])
+            .context("recording task history")?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    // history returns all recorded lane changes, ordered by task and time
+    pub(crate) fn history(&self) -> Result<Vec<Transition>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT task_id, state, at FROM task_history ORDER BY task_id, at, rowid")
+            .context("loading task history")?;
+        let rows = stmt
+            .query_map([], |r| {
+                Ok((
+                    r.get::<usize, i64>(0)?,
+                    r.get::<usize, i32>(1)?,
+                    r.get::<usize, i64>(2)?,
+                ))
+            })
+            .context("querying task history")?;
+        let mut history = Vec::new();
+        for row in rows {
+            let (task_id, state, at) = row?;
+            if let Some(at) = from_timestamp(at) {
+                history.push(Transition {
+                    task_id: task_id as u64,
+                    state: state.into(),
+                    at,
+                });
+            }
+        }
+        Ok(history)
+    }
+
+    // save_task persists task in DB, stamping its modification time. Sets task.id if needed
+    pub(crate) fn save_task(&mut self, task: &mut Task) -> Result<()> {
+        let now = Utc::now();
+        task.created_at.get_or_insert(now);
+        task.updated_at = Some(now);
+        if task.state == TaskState::Done {
+            task.completed_at.get_or_insert(now);
+        } else {
+            task.completed_at = None;
+        }
+        let tx = self.conn.transaction().context("starting transaction")?;
+        write_task(&tx, task)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    // assign_uuids generates UUIDs for tasks not having one, so they can be recognized when
+    // imported back from other tools
+    pub(crate) fn assign_uuids(&mut self) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        {
+            let mut stmt = tx.prepare("SELECT id FROM tasks WHERE uuid IS NULL")?;
+            let ids = stmt
+                .query_map([], |r| r.get::<usize, i64>(0))?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            let mut stmt = tx.prepare("UPDATE tasks SET uuid = ? WHERE id = ?")?;
+            for id in ids {
+                stmt.execute(params![Uuid::new_v4().to_string(), id])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    // import_tasks persists tasks in DB in single transaction, keeping timestamps they came with.
+    // Task is added as new one, unless there is already task with the same UUID, which is
+    // updated then
+    pub(crate) fn import_tasks(&mut self, tasks: &mut [Task]) -> Result<()> {
+        let tx = self.conn.transaction().context("starting transaction")?;
+        for task in tasks {
+            import_task(&tx, task).with_context(|| format!("importing task {:?}", task.title))?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+fn import_task(conn: &Connection, task: &mut Task) -> Result<()> {
+    let now = Utc::now();
+    task.id = match &task.uuid {
+        Some(uuid) => conn
+            .query_row("SELECT id FROM tasks WHERE uuid = ?", [uuid], |r| {
+                r.get::<usize, i64>(0)
+            })
+            .optional()?
+            .map(|id| id as u64),
+        None => None,
+    };
+    task.created_at.get_or_insert(now);
+    task.updated_at.get_or_insert(task.created_at.unwrap());
+    if task.state == TaskState::Done {
+        task.completed_at.get_or_insert(task.updated_at.unwrap());
+    } else {
+        task.completed_at = None;
+    }
+    write_task(conn, task)
+}
+
+// write_task stores task along with its tags and history, expected to run within transaction
+fn write_task(conn: &Connection, task: &mut Task) -> Result<()> {
+    let created_at = task.created_at.map(|t| t.timestamp());
+    let updated_at = task.updated_at.map(|t| t.timestamp());
+    let completed_at = task.completed_at.map(|t| t.timestamp());
+    let update_sql = format!(
+        "UPDATE tasks SET state=?,title=?,description=?,due=?,updated_at=?,{COMPLETED_AT} WHERE id=?"
+    );
+    let (sql, params) = match task.id {
+        Some(id) => (
+            update_sql.as_str(),
+            params![
+                task.state as i32,
+                task.title,
+                task.description,
+                task.due,
+                updated_at,
+                task.state as i32,
+                completed_at,
+                id as i64
+            ],
+        ),
+        None => (
+            "INSERT INTO tasks (state, title, description, due, created_at, updated_at, completed_at, uuid) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                task.state as i32,
+                task.title,
+                task.description,
+                task.due,
+                created_at,
+                updated_at,
+                completed_at,
+                task.uuid,
+            ],
+        ),
+    };
+
+    // new tasks enter their lane when created, and Done when completed
+    let entered_at = match task.state {
+        TaskState::Done => completed_at,
+        _ if task.id.is_none() => created_at,
+        _ => updated_at,
+    };
+
+    conn.execute(sql, params).context("saving task")?;
+    let id = match task.id {
+        Some(id) => id,
+        None => conn.last_insert_rowid() as u64,
+    };
+    if let Some(at) = entered_at.or(updated_at) {
+        conn.execute(RECORD_STATE, params![id as i64, task.state as i32, at])
+            .context("recording task history")?;
+    }
+
+    let mut stmt = conn
+        .prepare("SELECT tag FROM tags WHERE task_id=?")
+        .context("querying tags")?;
+    let mut old_tags: HashSet<String> = HashSet::new();
+    for row in stmt
+        .query_map([id as i64], |r| r.get(0))
+        .context("querying tags")?
+    {
+        old_tags.insert(row?);
+    }
+    let new_tags = HashSet::from_iter(task.tags.iter().cloned());
+    let tags_to_remove = old_tags.difference(&new_tags);
+    let tags_to_add = new_tags.difference(&old_tags);
+    let mut stmt = conn
+        .prepare("INSERT INTO tags (tag, task_id) VALUES (?, ?)")
+        .context("inserting new task tags")?;
+    for tag in tags_to_add {
+        stmt.execute(params![tag, id as i64])
+            .context("inserting new tags")?;
+    }
+    let mut stmt = conn
+        .prepare("DELETE FROM tags WHERE tag = ? AND task_id = ?")
+        .context("removing task old tags")?;
+    for tag in tags_to_remove {
+        stmt.execute(params![tag, id as i64])
+            .context("removing task old tags")?;
+    }
+
+    task.id = Some(id);
+    Ok(())
+}
+
+fn from_timestamp(ts: i64) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(ts, 0)
+}
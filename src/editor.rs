@@ -0,0 +1,117 @@
+use std::{
+    env,
+    fs::{self, OpenOptions},
+    io::Write,
+    process::Command,
+};
+
+use anyhow::{Context, Result, anyhow};
+use uuid::Uuid;
+
+// Delimiter of front matter in task document
+const FRONT_MATTER: &str = "---";
+
+// edit opens text in user's editor ($VISUAL, $EDITOR or vi) and returns edited text.
+// Expects terminal to be in its normal mode
+pub(crate) fn edit(text: &str) -> Result<String> {
+    let editor = ["VISUAL", "EDITOR"]
+        .into_iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|e| !e.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+    // editor may come with arguments, like "code --wait"
+    let mut args = editor.split_whitespace();
+    let program = args.next().unwrap();
+
+    // file gets unpredictable name and is never opened if it already exists, so nobody can
+    // plant a symlink in its place
+    let path = env::temp_dir().join(format!("awdy-{}.md", Uuid::new_v4()));
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options
+        .open(&path)
+        .with_context(|| format!("creating {}", path.display()))?;
+    let run = || -> Result<String> {
+        file.write_all(text.as_bytes())
+            .with_context(|| format!("writing {}", path.display()))?;
+        drop(file);
+        let status = Command::new(program)
+            .args(args)
+            .arg(&path)
+            .status()
+            .with_context(|| format!("running editor {editor:?}"))?;
+        if !status.success() {
+            return Err(anyhow!("editor {editor:?} exited with {status}"));
+        }
+        fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))
+    };
+    let edited = run();
+    let _ = fs::remove_file(&path);
+    edited
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum EditTarget {
+    Description,
+    // whole task as a document
+    Task,
+}
+
+// Document is task as edited in editor: fields in front matter followed by description
+#[derive(Default)]
+pub(crate) struct Document {
+    pub(crate) title: String,
+    pub(crate) tags: String,
+    pub(crate) due: String,
+    pub(crate) description: String,
+}
+
+impl Document {
+    pub(crate) fn render(&self) -> String {
+        format!(
+            "{FRONT_MATTER}\ntitle: {}\ntags: {}\ndue: {}\n{FRONT_MATTER}\n{}\n",
+            self.title, self.tags, self.due, self.description
+        )
+    }
+
+    pub(crate) fn parse(text: &str) -> Result<Self> {
+        let mut lines = text.lines().enumerate();
+        match lines.next() {
+            Some((_, line)) if line.trim_end() == FRONT_MATTER => {}
+            _ => return Err(anyhow!("document must start with {FRONT_MATTER:?} line")),
+        }
+        let mut doc = Self::default();
+        loop {
+            let Some((idx, line)) = lines.next() else {
+                return Err(anyhow!(
+                    "front matter is not closed with {FRONT_MATTER:?} line"
+                ));
+            };
+            if line.trim_end() == FRONT_MATTER {
+                break;
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| anyhow!("line {}: expected \"field: value\"", idx + 1))?;
+            let field = match key.trim() {
+                "title" => &mut doc.title,
+                "tags" => &mut doc.tags,
+                "due" => &mut doc.due,
+                key => return Err(anyhow!("line {}: unknown field {key:?}", idx + 1)),
+            };
+            *field = value.trim().to_string();
+        }
+        doc.description = lines
+            .map(|(_, line)| line)
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string();
+        Ok(doc)
+    }
+}
@@ -1,9 +1,10 @@
 use ratatui::{
     layout::{Constraint, Flex, Layout},
-    style::{Style, Stylize},
     widgets::{Block, Clear, Paragraph, Widget},
 };
 
+use crate::color_scheme::colors;
+
 pub(crate) struct ErrorWidget {
     pub(crate) title: String,
     pub(crate) message: String,
@@ -14,11 +15,8 @@ impl Widget for ErrorWidget {
         let lines: Vec<_> = self.message.split("\n").collect();
         let w = lines.iter().map(|l| l.len()).max().unwrap();
         let h = lines.len();
-        let t = Paragraph::new(lines.join("\n")).block(
-            Block::bordered()
-                .title(self.title)
-                .style(Style::new().black().on_blue()),
-        );
+        let t = Paragraph::new(lines.join("\n"))
+            .block(Block::bordered().title(self.title).style(colors().error()));
         let hor = Layout::horizontal([Constraint::Max(2 + w as u16)]).flex(Flex::Center);
         let ver = Layout::vertical([Constraint::Max(2 + h as u16)]).flex(Flex::Center);
         let [area] = ver.areas(area);
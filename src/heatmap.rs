@@ -0,0 +1,147 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::{self, Write},
+    path::Path,
+};
+
+use anyhow::Result;
+use chrono::{Datelike, Days, Local, NaiveDate};
+use ratatui::{
+    style::Style,
+    text::{Line, Span},
+};
+
+use crate::{
+    db::Db,
+    model::{TaskState, Transition},
+};
+
+// Number of weeks shown at most, a year
+pub(crate) const WEEKS: usize = 53;
+// Width of weekday labels column
+const LABEL_WIDTH: usize = 4;
+// Symbols of cells, from days without completed tasks to the busiest ones
+const LEVELS: [char; 5] = ['·', '░', '▒', '▓', '█'];
+
+// Heatmap shows how many tasks were completed each day, GitHub contributions style: one column per
+// week, one row per weekday
+pub(crate) struct Heatmap {
+    counts: HashMap<NaiveDate, u64>,
+    today: NaiveDate,
+}
+
+impl Heatmap {
+    // new counts given tasks moved to Done each day
+    pub(crate) fn new(history: &[Transition], tasks: &HashSet<u64>) -> Self {
+        let mut counts = HashMap::new();
+        for t in history {
+            if t.state == TaskState::Done && tasks.contains(&t.task_id) {
+                *counts
+                    .entry(t.at.with_timezone(&Local).date_naive())
+                    .or_default() += 1;
+            }
+        }
+        Self {
+            counts,
+            today: Local::now().date_naive(),
+        }
+    }
+
+    // width returns number of columns taken by heatmap of given number of weeks
+    pub(crate) fn width(weeks: usize) -> usize {
+        LABEL_WIDTH + 2 * weeks
+    }
+
+    // lines renders heatmap of given number of weeks, ending with the current one. Days with
+    // completed tasks are drawn with active style, the rest with empty one
+    pub(crate) fn lines(&self, weeks: usize, active: Style, empty: Style) -> Vec<Line<'static>> {
+        let weeks = weeks.clamp(1, WEEKS);
+        let this_week = self.today - Days::new(self.today.weekday().num_days_from_monday() as u64);
+        let first = this_week - Days::new(7 * (weeks as u64 - 1));
+        let monday = |week: usize| first + Days::new(7 * week as u64);
+        let days = first.iter_days().take_while(|d| *d <= self.today);
+        let max = days
+            .clone()
+            .filter_map(|d| self.counts.get(&d))
+            .max()
+            .copied()
+            .unwrap_or(0);
+        let total: u64 = days.filter_map(|d| self.counts.get(&d)).sum();
+
+        // month is labeled above the first week ending in it, if there is room for the label
+        let mut months = " ".repeat(LABEL_WIDTH);
+        for week in 0..weeks {
+            let sunday = monday(week) + Days::new(6);
+            let starts = week == 0 || sunday.month() != (sunday - Days::new(7)).month();
+            let column = LABEL_WIDTH + 2 * week;
+            let label = sunday.format("%b").to_string();
+            if starts
+                && months.chars().count() <= column
+                && column + label.len() <= Self::width(weeks)
+            {
+                months.push_str(&" ".repeat(column - months.chars().count()));
+                months.push_str(&label);
+            }
+        }
+        let mut lines = vec![Line::from(months)];
+
+        for weekday in 0..7 {
+            let label = match weekday {
+                0 => "Mon",
+                2 => "Wed",
+                4 => "Fri",
+                _ => "",
+            };
+            let mut spans = vec![Span::raw(format!("{label:LABEL_WIDTH$}"))];
+            for week in 0..weeks {
+                let day = monday(week) + Days::new(weekday);
+                if day > self.today {
+                    break;
+                }
+                let count = self.counts.get(&day).copied().unwrap_or(0);
+                let level = match count {
+                    0 => 0,
+                    _ => (count * 4).div_ceil(max).clamp(1, 4) as usize,
+                };
+                let style = if level == 0 { empty } else { active };
+                spans.push(Span::styled(format!("{} ", LEVELS[level]), style));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        let period = if weeks == WEEKS {
+            "the last year".to_string()
+        } else {
+            format!("the last {weeks} weeks")
+        };
+        let mut legend = vec![Span::raw(format!(
+            "{total} tasks completed in {period}  Less "
+        ))];
+        for (level, symbol) in LEVELS.iter().enumerate() {
+            let style = if level == 0 { empty } else { active };
+            legend.push(Span::styled(format!("{symbol} "), style));
+        }
+        legend.push(Span::raw("More"));
+        lines.push(Line::default());
+        lines.push(Line::from(legend));
+        lines
+    }
+}
+
+// print prints heatmap of the last year to stdout. Only tasks having any of given tags are
+// counted, or all but archived ones if no tags given, like in statistics view
+pub fn print(db_path: &Path, tags: &[String]) -> Result<()> {
+    let db = Db::open(db_path)?;
+    let tasks: HashSet<u64> = db
+        .filtered_tasks(tags)?
+        .iter()
+        .filter_map(|t| t.id)
+        .collect();
+    let heatmap = Heatmap::new(&db.history()?, &tasks);
+    let mut out = io::stdout().lock();
+    for line in heatmap.lines(WEEKS, Style::new(), Style::new()) {
+        let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        writeln!(out, "{}", text.trim_end())?;
+    }
+    Ok(())
+}
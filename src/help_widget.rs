@@ -0,0 +1,82 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Margin, Rect},
+    text::{Line, Span},
+    widgets::{
+        Block, BorderType, Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+        StatefulWidget, Widget,
+    },
+};
+
+use crate::{
+    color_scheme::colors,
+    keymap::{Scope, keymap},
+    model::SelectedPane,
+};
+
+pub(crate) struct HelpState {
+    pub(crate) scroll: usize,
+}
+
+// HelpWidget is modal window listing keys bound in given scope and pane
+pub(crate) struct HelpWidget<'a> {
+    pub(crate) title: &'a str,
+    pub(crate) scope: Scope,
+    pub(crate) pane: &'a SelectedPane,
+}
+
+impl StatefulWidget for HelpWidget<'_> {
+    type State = HelpState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let colors = colors();
+        let bindings: Vec<_> = keymap()
+            .bindings(self.scope, self.pane)
+            .map(|(action, keys)| {
+                let keys: Vec<_> = keys.iter().map(|k| k.to_string()).collect();
+                (keys.join(", "), action.description())
+            })
+            .collect();
+        let keys_width = bindings.iter().map(|(k, _)| k.len()).max().unwrap_or(0);
+        let lines: Vec<_> = bindings
+            .into_iter()
+            .map(|(keys, description)| {
+                Line::from(vec![
+                    Span::styled(format!("{keys:>keys_width$}"), colors.tag()),
+                    Span::raw("  "),
+                    Span::raw(description),
+                ])
+            })
+            .collect();
+        let width = lines.iter().map(|l| l.width()).max().unwrap_or(0);
+
+        // 2 for borders, 2 for padding, 1 for scrollbar
+        let hor = Layout::horizontal([Constraint::Max(width as u16 + 5)]).flex(Flex::Center);
+        let ver = Layout::vertical([Constraint::Max(lines.len() as u16 + 2)]).flex(Flex::Center);
+        let [area] = ver.areas(area);
+        let [area] = hor.areas(area);
+        let block = Block::bordered()
+            .border_type(BorderType::Double)
+            .border_style(colors.active_border())
+            .title(format!(" {} ", self.title))
+            .title_alignment(ratatui::layout::Alignment::Center)
+            .title_style(colors.lane_active_title())
+            .title_bottom(Line::from(" Esc to close ").right_aligned());
+        let inner = block.inner(area);
+        let overflow = lines.len().saturating_sub(inner.height as usize);
+        state.scroll = state.scroll.min(overflow);
+
+        Clear.render(area, buf);
+        block.style(colors.text()).render(area, buf);
+        Paragraph::new(lines)
+            .scroll((state.scroll as u16, 0))
+            .render(inner.inner(Margin::new(1, 0)), buf);
+        if overflow > 0 {
+            let mut scrollbar = ScrollbarState::new(overflow).position(state.scroll);
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .render(inner, buf, &mut scrollbar);
+        }
+    }
+}
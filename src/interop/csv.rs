@@ -0,0 +1,250 @@
+use std::io::{Read, Write};
+
+use anyhow::{Context, Result, anyhow};
+use csv::{ReaderBuilder, StringRecord, Writer};
+
+use super::{ImportOptions, format_datetime, parse_datetime};
+use crate::model::Task;
+
+const FIELDS: [&str; 10] = [
+    "id",
+    "uuid",
+    "lane",
+    "title",
+    "description",
+    "tags",
+    "due",
+    "created_at",
+    "updated_at",
+    "completed_at",
+];
+
+pub(crate) fn export(tasks: &[Task], out: impl Write) -> Result<()> {
+    let mut w = Writer::from_writer(out);
+    w.write_record(FIELDS)?;
+    for task in tasks {
+        w.write_record([
+            task.id.map(|id| id.to_string()).unwrap_or_default(),
+            task.uuid.clone().unwrap_or_default(),
+            task.state.to_string(),
+            task.title.clone(),
+            task.description.clone().unwrap_or_default(),
+            join_tags(&task.tags),
+            task.due.map(|d| d.to_string()).unwrap_or_default(),
+            task.created_at
+                .as_ref()
+                .map(format_datetime)
+                .unwrap_or_default(),
+            task.updated_at
+                .as_ref()
+                .map(format_datetime)
+                .unwrap_or_default(),
+            task.completed_at
+                .as_ref()
+                .map(format_datetime)
+                .unwrap_or_default(),
+        ])?;
+    }
+    w.flush()?;
+    Ok(())
+}
+
+// join_tags puts tags into single comma-separated cell, escaping commas and backslashes in tags
+fn join_tags(tags: &[String]) -> String {
+    tags.iter()
+        .map(|t| t.replace('\\', "\\\\").replace(',', "\\,"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+// split_tags is the reverse of join_tags
+fn split_tags(cell: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    let mut current = String::new();
+    let mut chars = cell.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => current.extend(chars.next()),
+            ',' => tags.push(std::mem::take(&mut current)),
+            c => current.push(c),
+        }
+    }
+    tags.push(current);
+    tags.into_iter()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+// Column index for every task field, resolved from CSV header and user-provided mapping
+struct Columns {
+    uuid: Option<usize>,
+    lane: Option<usize>,
+    title: usize,
+    description: Option<usize>,
+    tags: Option<usize>,
+    due: Option<usize>,
+    created_at: Option<usize>,
+    updated_at: Option<usize>,
+    completed_at: Option<usize>,
+}
+
+impl Columns {
+    fn resolve(header: &StringRecord, options: &ImportOptions) -> Result<Self> {
+        for (field, _) in &options.columns {
+            if !FIELDS.contains(&field.as_str()) {
+                return Err(anyhow!(
+                    "unknown field {field:?}, expected one of {}",
+                    FIELDS.join(", ")
+                ));
+            }
+        }
+        let find = |field: &str| -> Result<Option<usize>> {
+            let mapped = options.columns.iter().rfind(|(f, _)| f == field);
+            let column = mapped.map_or(field, |(_, c)| c.as_str());
+            let idx = header
+                .iter()
+                .position(|h| h.trim().eq_ignore_ascii_case(column));
+            match (idx, mapped) {
+                (None, Some(_)) => Err(anyhow!("column {column:?} not found in CSV header")),
+                (idx, _) => Ok(idx),
+            }
+        };
+        Ok(Self {
+            uuid: find("uuid")?,
+            lane: find("lane")?,
+            title: find("title")?.ok_or_else(|| anyhow!("CSV has no title column"))?,
+            description: find("description")?,
+            tags: find("tags")?,
+            due: find("due")?,
+            created_at: find("created_at")?,
+            updated_at: find("updated_at")?,
+            completed_at: find("completed_at")?,
+        })
+    }
+}
+
+pub(crate) fn import(input: impl Read, options: &ImportOptions) -> Result<Vec<Task>> {
+    let mut r = ReaderBuilder::new().flexible(true).from_reader(input);
+    let columns = Columns::resolve(r.headers().context("reading CSV header")?, options)?;
+
+    let mut tasks = Vec::new();
+    for record in r.records() {
+        let record = record?;
+        let line = record.position().map_or(0, |p| p.line());
+        let parse = || -> Result<Task> {
+            let get = |idx: Option<usize>| {
+                idx.and_then(|i| record.get(i))
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty())
+            };
+            Ok(Task {
+                state: get(columns.lane)
+                    .map(str::parse)
+                    .transpose()?
+                    .unwrap_or_default(),
+                title: get(Some(columns.title))
+                    .ok_or_else(|| anyhow!("empty title"))?
+                    .to_string(),
+                description: get(columns.description).map(String::from),
+                tags: get(columns.tags).map(split_tags).unwrap_or_default(),
+                due: get(columns.due)
+                    .map(|d| parse_datetime(d).map(|t| t.date_naive()))
+                    .transpose()?,
+                created_at: get(columns.created_at).map(parse_datetime).transpose()?,
+                updated_at: get(columns.updated_at).map(parse_datetime).transpose()?,
+                completed_at: get(columns.completed_at).map(parse_datetime).transpose()?,
+                uuid: get(columns.uuid).map(String::from),
+                ..Default::default()
+            })
+        };
+        tasks.push(parse().with_context(|| format!("line {line}"))?);
+    }
+    Ok(tasks)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::model::TaskState;
+
+    fn round_trip(tasks: &[Task]) -> Vec<Task> {
+        let mut out = Vec::new();
+        export(tasks, &mut out).unwrap();
+        import(out.as_slice(), &ImportOptions::default()).unwrap()
+    }
+
+    #[test]
+    fn tags_with_commas_round_trip() {
+        let task = Task {
+            state: TaskState::InProgress,
+            title: "fix, then ship".to_string(),
+            description: Some("line one\nline two".to_string()),
+            tags: vec![
+                "a,b".to_string(),
+                "back\\slash".to_string(),
+                "plain".to_string(),
+            ],
+            due: NaiveDate::from_ymd_opt(2026, 3, 1),
+            created_at: Some(parse_datetime("2026-01-02T03:04:05Z").unwrap()),
+            uuid: Some("3f2504e0-4f89-11d3-9a0c-0305e82c3301".to_string()),
+            ..Default::default()
+        };
+        let tasks = round_trip(std::slice::from_ref(&task));
+        assert_eq!(tasks.len(), 1);
+        let imported = &tasks[0];
+        assert_eq!(imported.state, task.state);
+        assert_eq!(imported.title, task.title);
+        assert_eq!(imported.description, task.description);
+        assert_eq!(imported.tags, task.tags);
+        assert_eq!(imported.due, task.due);
+        assert_eq!(imported.created_at, task.created_at);
+        assert_eq!(imported.uuid, task.uuid);
+    }
+
+    #[test]
+    fn split_tags_skips_empty_ones() {
+        assert_eq!(split_tags(" a , ,b\\,c,"), vec!["a", "b,c"]);
+        assert_eq!(join_tags(&["x,y".to_string(), "z".to_string()]), "x\\,y,z");
+    }
+
+    #[test]
+    fn mapped_columns() {
+        let input = "Summary,Status,Labels,Extra\nWrite docs,done,\"docs, team/a\",x\n";
+        let options = ImportOptions {
+            columns: vec![
+                ("title".to_string(), "Summary".to_string()),
+                ("lane".to_string(), "status".to_string()),
+                ("tags".to_string(), "Labels".to_string()),
+            ],
+        };
+        let tasks = import(input.as_bytes(), &options).unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "Write docs");
+        assert_eq!(tasks[0].state, TaskState::Done);
+        assert_eq!(tasks[0].tags, vec!["docs", "team/a"]);
+    }
+
+    #[test]
+    fn mapping_errors() {
+        let options = ImportOptions {
+            columns: vec![("title".to_string(), "Missing".to_string())],
+        };
+        assert!(import("title\nx\n".as_bytes(), &options).is_err());
+        let options = ImportOptions {
+            columns: vec![("nonsense".to_string(), "title".to_string())],
+        };
+        assert!(import("title\nx\n".as_bytes(), &options).is_err());
+        assert!(import("lane\nTODO\n".as_bytes(), &ImportOptions::default()).is_err());
+    }
+
+    #[test]
+    fn empty_title_is_error() {
+        let err = import("title,lane\n ,TODO\n".as_bytes(), &ImportOptions::default())
+            .err()
+            .unwrap();
+        assert_eq!(format!("{err:#}"), "line 2: empty title");
+    }
+}
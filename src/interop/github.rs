@@ -0,0 +1,129 @@
+use std::io::Read;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::model::{Task, TaskState};
+
+// Issue as returned by GitHub REST API or by `gh issue list --json ...`
+#[derive(Deserialize)]
+struct Issue {
+    number: Option<u64>,
+    title: String,
+    #[serde(default)]
+    body: Option<String>,
+    state: String,
+    #[serde(default)]
+    labels: Vec<Label>,
+    #[serde(alias = "createdAt")]
+    created_at: Option<DateTime<Utc>>,
+    #[serde(alias = "updatedAt")]
+    updated_at: Option<DateTime<Utc>>,
+    #[serde(alias = "closedAt")]
+    closed_at: Option<DateTime<Utc>>,
+    // REST API lists pull requests among issues, marking them with this field
+    #[serde(default)]
+    pull_request: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Label {
+    Name(String),
+    Object { name: String },
+}
+
+pub(crate) fn import(input: impl Read) -> Result<Vec<Task>> {
+    let issues: Vec<Issue> =
+        serde_json::from_reader(input).context("parsing GitHub issues list")?;
+    Ok(issues
+        .into_iter()
+        .filter(|issue| issue.pull_request.is_none())
+        .map(|issue| {
+            let state = if issue.state.eq_ignore_ascii_case("closed") {
+                TaskState::Done
+            } else {
+                TaskState::Todo
+            };
+            let title = match issue.number {
+                Some(n) => format!("#{n} {}", issue.title),
+                None => issue.title,
+            };
+            Task {
+                state,
+                title,
+                description: issue
+                    .body
+                    .map(|b| b.trim().replace("\r\n", "\n"))
+                    .filter(|b| !b.is_empty()),
+                tags: issue
+                    .labels
+                    .into_iter()
+                    .map(|l| match l {
+                        Label::Name(name) | Label::Object { name } => name,
+                    })
+                    .collect(),
+                created_at: issue.created_at,
+                updated_at: issue.updated_at,
+                completed_at: issue.closed_at,
+                ..Default::default()
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn import_rest_api_issues() {
+        let json = r#"[
+            {
+                "number": 12,
+                "title": "Crash on start",
+                "body": "Steps:\r\n1. run\r\n",
+                "state": "closed",
+                "labels": [{"name": "bug", "color": "d73a4a"}],
+                "created_at": "2026-01-01T10:00:00Z",
+                "closed_at": "2026-01-03T10:00:00Z"
+            },
+            {
+                "number": 13,
+                "title": "Fix crash",
+                "state": "open",
+                "pull_request": {"url": "https://api.github.com/repos/o/r/pulls/13"}
+            }
+        ]"#;
+        let tasks = import(json.as_bytes()).unwrap();
+        assert_eq!(tasks.len(), 1);
+        let task = &tasks[0];
+        assert_eq!(task.state, TaskState::Done);
+        assert_eq!(task.title, "#12 Crash on start");
+        assert_eq!(task.description.as_deref(), Some("Steps:\n1. run"));
+        assert_eq!(task.tags, vec!["bug"]);
+        assert!(task.created_at.is_some());
+        assert!(task.completed_at.is_some());
+    }
+
+    #[test]
+    fn import_gh_cli_issues() {
+        let json = r#"[
+            {
+                "title": "Add dark mode",
+                "body": "",
+                "state": "OPEN",
+                "labels": ["enhancement", {"name": "ui"}],
+                "createdAt": "2026-01-01T10:00:00Z"
+            }
+        ]"#;
+        let tasks = import(json.as_bytes()).unwrap();
+        let task = &tasks[0];
+        assert_eq!(task.state, TaskState::Todo);
+        assert_eq!(task.title, "Add dark mode");
+        assert_eq!(task.description, None);
+        assert_eq!(task.tags, vec!["enhancement", "ui"]);
+        assert!(task.created_at.is_some());
+    }
+}
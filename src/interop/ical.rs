@@ -0,0 +1,337 @@
+use std::io::{BufRead, BufReader, Read, Write};
+
+use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+use crate::{
+    config::config,
+    model::{Task, TaskState},
+};
+
+// iCalendar statuses can't tell TODO from Blocked, so exact lane is kept in custom property
+const LANE_PROPERTY: &str = "X-AWDY-LANE";
+// RFC 5545 limits content lines to 75 octets, excluding line break
+const MAX_LINE_LEN: usize = 75;
+
+// Expects tasks to have UUIDs assigned
+pub(crate) fn export(tasks: &[Task], mut out: impl Write) -> Result<()> {
+    let now = format_datetime(&Utc::now());
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//awdy//awdy//EN".to_string(),
+    ];
+    for task in tasks {
+        lines.push("BEGIN:VTODO".to_string());
+        lines.push(format!("UID:{}", task.uuid.as_deref().unwrap_or_default()));
+        lines.push(format!("DTSTAMP:{now}"));
+        lines.push(format!("SUMMARY:{}", escape(&task.title)));
+        if let Some(description) = &task.description {
+            lines.push(format!("DESCRIPTION:{}", escape(description)));
+        }
+        let categories: Vec<_> = task.tags.iter().map(|t| escape(t)).collect();
+        if !categories.is_empty() {
+            lines.push(format!("CATEGORIES:{}", categories.join(",")));
+        }
+        let status = match task.state {
+            _ if task.tags.iter().any(|t| *t == config().archive_tag)
+                && task.state != TaskState::Done =>
+            {
+                "CANCELLED"
+            }
+            TaskState::Todo | TaskState::Blocked => "NEEDS-ACTION",
+            TaskState::InProgress => "IN-PROCESS",
+            TaskState::Done => "COMPLETED",
+        };
+        lines.push(format!("STATUS:{status}"));
+        lines.push(format!("{LANE_PROPERTY}:{}", escape(task.state.into())));
+        if let Some(due) = task.due {
+            lines.push(format!("DUE;VALUE=DATE:{}", due.format("%Y%m%d")));
+        }
+        if let Some(t) = &task.created_at {
+            lines.push(format!("CREATED:{}", format_datetime(t)));
+        }
+        if let Some(t) = &task.updated_at {
+            lines.push(format!("LAST-MODIFIED:{}", format_datetime(t)));
+        }
+        if let Some(t) = &task.completed_at {
+            lines.push(format!("COMPLETED:{}", format_datetime(t)));
+        }
+        lines.push("END:VTODO".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    for line in lines {
+        write!(out, "{}\r\n", fold(&line))?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+pub(crate) fn import(input: impl Read) -> Result<Vec<Task>> {
+    let mut tasks = Vec::new();
+    let mut current: Option<Task> = None;
+    let mut lane = None;
+    // depth of components nested into VTODO (like VALARM), whose properties are skipped
+    let mut nested = 0;
+    for (line_no, line) in unfold(input)? {
+        let property = parse_property(&line).with_context(|| format!("line {line_no}"))?;
+        let Some(task) = current.as_mut() else {
+            if property.name == "BEGIN" && property.value.eq_ignore_ascii_case("VTODO") {
+                current = Some(Task::default());
+                lane = None;
+            }
+            continue;
+        };
+        let value = property.value;
+        match property.name.as_str() {
+            "BEGIN" => nested += 1,
+            "END" if nested > 0 => nested -= 1,
+            _ if nested > 0 => {}
+            "END" => {
+                let mut task = current.take().unwrap();
+                if let Some(lane) = lane.take() {
+                    task.state = lane;
+                }
+                tasks.push(task);
+            }
+            "UID" => task.uuid = Some(value),
+            "SUMMARY" => task.title = unescape(&value),
+            "DESCRIPTION" => {
+                let description = unescape(&value);
+                task.description = (!description.trim().is_empty()).then_some(description);
+            }
+            "CATEGORIES" => task.tags.extend(
+                split_list(&value)
+                    .iter()
+                    .map(|t| unescape(t).trim().to_string())
+                    .filter(|t| !t.is_empty()),
+            ),
+            "STATUS" => match value.to_ascii_uppercase().as_str() {
+                "IN-PROCESS" => task.state = TaskState::InProgress,
+                "COMPLETED" => task.state = TaskState::Done,
+                "CANCELLED" => task.tags.push(config().archive_tag.clone()),
+                _ => task.state = TaskState::Todo,
+            },
+            LANE_PROPERTY => lane = unescape(&value).parse().ok(),
+            "DUE" => task.due = parse_datetime(&value).map(|t| t.date_naive()),
+            "CREATED" => task.created_at = parse_datetime(&value),
+            "LAST-MODIFIED" => task.updated_at = parse_datetime(&value),
+            "COMPLETED" => task.completed_at = parse_datetime(&value),
+            _ => {}
+        }
+    }
+    if current.is_some() {
+        return Err(anyhow!("unterminated VTODO"));
+    }
+    for task in &mut tasks {
+        task.tags.dedup();
+    }
+    Ok(tasks)
+}
+
+struct Property {
+    name: String,
+    value: String,
+}
+
+// parse_property splits content line into name and value, ignoring property parameters
+fn parse_property(line: &str) -> Result<Property> {
+    let mut in_quotes = false;
+    for (idx, c) in line.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ':' if !in_quotes => {
+                let name = &line[..idx];
+                let name = name.split(';').next().unwrap_or_default();
+                return Ok(Property {
+                    name: name.trim().to_ascii_uppercase(),
+                    value: line[idx + 1..].to_string(),
+                });
+            }
+            _ => {}
+        }
+    }
+    Err(anyhow!("invalid content line {line:?}"))
+}
+
+// unfold joins folded content lines, returning each along with number of its first physical line
+fn unfold(input: impl Read) -> Result<Vec<(usize, String)>> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    for (idx, line) in BufReader::new(input).lines().enumerate() {
+        let line = line?;
+        let line = line.trim_end_matches('\r');
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some((_, last))) => last.push_str(continuation),
+            _ if line.is_empty() => {}
+            _ => lines.push((idx + 1, line.to_string())),
+        }
+    }
+    Ok(lines)
+}
+
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut len = 0;
+    for c in line.chars() {
+        if len + c.len_utf8() > MAX_LINE_LEN {
+            folded.push_str("\r\n ");
+            len = 1;
+        }
+        folded.push(c);
+        len += c.len_utf8();
+    }
+    folded
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+fn unescape(s: &str) -> String {
+    let mut r = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            r.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => r.push('\n'),
+            Some(c) => r.push(c),
+            None => r.push('\\'),
+        }
+    }
+    r
+}
+
+// split_list splits value on commas that are not escaped
+fn split_list(s: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+    for (idx, c) in s.char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            ',' if !escaped => {
+                items.push(&s[start..idx]);
+                start = idx + 1;
+            }
+            _ => escaped = false,
+        }
+    }
+    items.push(&s[start..]);
+    items
+}
+
+fn format_datetime(t: &DateTime<Utc>) -> String {
+    t.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+// parse_datetime accepts UTC, floating (taken as UTC) date-times and plain dates
+fn parse_datetime(s: &str) -> Option<DateTime<Utc>> {
+    let s = s.trim();
+    if let Ok(t) = NaiveDateTime::parse_from_str(s.trim_end_matches('Z'), "%Y%m%dT%H%M%S") {
+        return Some(t.and_utc());
+    }
+    NaiveDate::parse_from_str(s, "%Y%m%d")
+        .ok()
+        .map(|d| d.and_hms_opt(0, 0, 0).unwrap().and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(s: &str) -> DateTime<Utc> {
+        NaiveDateTime::parse_from_str(s, "%Y%m%dT%H%M%SZ")
+            .unwrap()
+            .and_utc()
+    }
+
+    #[test]
+    fn round_trip() {
+        let tasks = vec![
+            Task {
+                state: TaskState::Blocked,
+                title: "Long title, with; special \\ characters ".repeat(3),
+                description: Some("First line\nSecond line".to_string()),
+                tags: vec!["a,b".to_string(), "c".to_string()],
+                created_at: Some(time("20260101T100000Z")),
+                updated_at: Some(time("20260102T100000Z")),
+                uuid: Some("task-1@example.com".to_string()),
+                due: NaiveDate::from_ymd_opt(2026, 2, 1),
+                ..Default::default()
+            },
+            Task {
+                state: TaskState::Done,
+                title: "Done".to_string(),
+                completed_at: Some(time("20260103T090000Z")),
+                uuid: Some("task-2@example.com".to_string()),
+                ..Default::default()
+            },
+        ];
+        let mut out = Vec::new();
+        export(&tasks, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.split("\r\n").all(|line| line.len() <= MAX_LINE_LEN));
+
+        let imported = import(text.as_bytes()).unwrap();
+        assert_eq!(imported.len(), 2);
+        for (imported, task) in imported.iter().zip(&tasks) {
+            assert_eq!(imported.state, task.state);
+            assert_eq!(imported.title, task.title);
+            assert_eq!(imported.description, task.description);
+            assert_eq!(imported.tags, task.tags);
+            assert_eq!(imported.uuid, task.uuid);
+            assert_eq!(imported.due, task.due);
+            assert_eq!(imported.created_at, task.created_at);
+            assert_eq!(imported.updated_at, task.updated_at);
+            assert_eq!(imported.completed_at, task.completed_at);
+        }
+    }
+
+    #[test]
+    fn import_from_other_clients() {
+        let text = "BEGIN:VCALENDAR\r\n\
+                    BEGIN:VTODO\r\n\
+                    UID:abc-123\r\n\
+                    DTSTART:20260101T080000Z\r\n\
+                    SUMMARY;LANGUAGE=en:Buy \r\n milk\r\n\
+                    STATUS:CANCELLED\r\n\
+                    DUE;TZID=Europe/Berlin:20260105T180000\r\n\
+                    BEGIN:VALARM\r\n\
+                    SUMMARY:Alarm\r\n\
+                    STATUS:COMPLETED\r\n\
+                    END:VALARM\r\n\
+                    END:VTODO\r\n\
+                    END:VCALENDAR\r\n";
+        let tasks = import(text.as_bytes()).unwrap();
+        assert_eq!(tasks.len(), 1);
+        let task = &tasks[0];
+        assert_eq!(task.uuid.as_deref(), Some("abc-123"));
+        assert_eq!(task.title, "Buy milk");
+        assert_eq!(task.state, TaskState::Todo);
+        assert_eq!(task.tags, vec![config().archive_tag.clone()]);
+        assert_eq!(task.due, NaiveDate::from_ymd_opt(2026, 1, 5));
+    }
+
+    #[test]
+    fn errors_report_physical_line() {
+        let text = "BEGIN:VTODO\r\nSUMMARY:long\r\n  title\r\ninvalid\r\nEND:VTODO\r\n";
+        let err = import(text.as_bytes()).err().unwrap();
+        assert_eq!(
+            format!("{err:#}"),
+            "line 4: invalid content line \"invalid\""
+        );
+
+        let err = import("BEGIN:VTODO\r\nSUMMARY:x\r\n".as_bytes())
+            .err()
+            .unwrap();
+        assert_eq!(format!("{err:#}"), "unterminated VTODO");
+    }
+}
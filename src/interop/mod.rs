@@ -0,0 +1,211 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+    str::FromStr,
+};
+
+use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, Utc};
+
+use crate::{db::Db, model::Task};
+
+mod csv;
+mod github;
+mod ical;
+mod taskwarrior;
+mod todotxt;
+mod trello;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Format {
+    Csv,
+    TodoTxt,
+    Taskwarrior,
+    Ical,
+    Trello,
+    Github,
+}
+
+impl Format {
+    pub const EXPORT_NAMES: &[&str] = &["csv", "todotxt", "taskwarrior", "ical"];
+    pub const IMPORT_NAMES: &[&str] =
+        &["csv", "todotxt", "taskwarrior", "ical", "trello", "github"];
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "todotxt" => Ok(Self::TodoTxt),
+            "taskwarrior" => Ok(Self::Taskwarrior),
+            "ical" => Ok(Self::Ical),
+            "trello" => Ok(Self::Trello),
+            "github" => Ok(Self::Github),
+            _ => Err(anyhow!("unsupported format {s:?}")),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ImportOptions {
+    // (field, column) pairs overriding which source column is read into which task field
+    pub columns: Vec<(String, String)>,
+}
+
+// export writes all tasks from database to given file, or to stdout if no file given.
+// For iCalendar, output may be a directory, which then gets separate .ics file for every task
+pub fn export(db_path: &Path, format: Format, out: Option<&str>) -> Result<()> {
+    let mut db = Db::open(db_path)?;
+    if matches!(format, Format::Csv | Format::Taskwarrior | Format::Ical) {
+        db.assign_uuids().context("assigning task UUIDs")?;
+    }
+    let tasks = db.all_tasks().context("loading tasks")?;
+    if let (Format::Ical, Some(dir)) = (format, out)
+        && Path::new(dir).is_dir()
+    {
+        for task in &tasks {
+            let path = Path::new(dir).join(ical_file_name(task));
+            let file =
+                File::create(&path).with_context(|| format!("creating {}", path.display()))?;
+            ical::export(std::slice::from_ref(task), BufWriter::new(file))
+                .with_context(|| format!("exporting task #{}", task.id.unwrap()))?;
+        }
+        return Ok(());
+    }
+
+    let out: Box<dyn Write> = match out {
+        Some(path) if path != "-" => Box::new(BufWriter::new(
+            File::create(path).with_context(|| format!("creating {path}"))?,
+        )),
+        _ => Box::new(io::stdout().lock()),
+    };
+    match format {
+        Format::Csv => csv::export(&tasks, out),
+        Format::TodoTxt => todotxt::export(&tasks, out),
+        Format::Taskwarrior => taskwarrior::export(&tasks, out),
+        Format::Ical => ical::export(&tasks, out),
+        Format::Trello | Format::Github => {
+            Err(anyhow!("exporting to this format is not supported"))
+        }
+    }
+    .context("exporting tasks")
+}
+
+// ical_file_name names task's .ics file by its UID. UIDs may come from imported files, so
+// ones that are not safe to use as file name fall back to task id
+fn ical_file_name(task: &Task) -> String {
+    match task.uuid.as_deref() {
+        Some(uid)
+            if !uid.is_empty()
+                && !uid.starts_with('.')
+                && uid
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-')) =>
+        {
+            format!("{uid}.ics")
+        }
+        _ => format!("task-{}.ics", task.id.unwrap()),
+    }
+}
+
+// import reads tasks from given file (or stdin if it is "-") and adds them to database.
+// For iCalendar, file may be a directory, then all .ics files from it are imported
+pub fn import(
+    db_path: &Path,
+    format: Format,
+    file: &str,
+    options: &ImportOptions,
+) -> Result<usize> {
+    let mut tasks = if let Format::Ical = format
+        && Path::new(file).is_dir()
+    {
+        let mut tasks = Vec::new();
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(file).with_context(|| format!("listing {file}"))? {
+            let path = entry?.path();
+            if path
+                .extension()
+                .is_some_and(|e| e.eq_ignore_ascii_case("ics"))
+            {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        for path in paths {
+            let input = File::open(&path).with_context(|| format!("opening {}", path.display()))?;
+            tasks.extend(
+                ical::import(BufReader::new(input))
+                    .with_context(|| format!("reading {}", path.display()))?,
+            );
+        }
+        tasks
+    } else {
+        read_tasks(format, file, options)?
+    };
+
+    Db::open(db_path)?.import_tasks(&mut tasks)?;
+    Ok(tasks.len())
+}
+
+fn read_tasks(format: Format, file: &str, options: &ImportOptions) -> Result<Vec<Task>> {
+    let input: Box<dyn Read> = if file == "-" {
+        Box::new(io::stdin().lock())
+    } else {
+        Box::new(BufReader::new(
+            File::open(file).with_context(|| format!("opening {file}"))?,
+        ))
+    };
+    match format {
+        Format::Csv => csv::import(input, options),
+        Format::TodoTxt => todotxt::import(input),
+        Format::Taskwarrior => taskwarrior::import(input),
+        Format::Ical => ical::import(input),
+        Format::Trello => trello::import(input),
+        Format::Github => github::import(input),
+    }
+    .with_context(|| format!("reading {file}"))
+}
+
+pub(crate) fn format_datetime(t: &DateTime<Utc>) -> String {
+    t.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+// parse_datetime accepts RFC 3339 timestamps as well as plain dates and date-times (taken as UTC)
+pub(crate) fn parse_datetime(s: &str) -> Result<DateTime<Utc>> {
+    let s = s.trim();
+    if let Ok(t) = DateTime::parse_from_rfc3339(s) {
+        return Ok(t.to_utc());
+    }
+    for fmt in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Ok(t) = NaiveDateTime::parse_from_str(s, fmt) {
+            return Ok(t.and_utc());
+        }
+    }
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map(|d| d.and_hms_opt(0, 0, 0).unwrap().and_utc())
+        .map_err(|_| anyhow!("invalid timestamp {s:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ical_file_names_stay_in_directory() {
+        let task = |uid: &str| Task {
+            id: Some(7),
+            uuid: Some(uid.to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            ical_file_name(&task("6fa4b0a2-3e0d-4a1c-9c1e-1b2a3c4d5e6f")),
+            "6fa4b0a2-3e0d-4a1c-9c1e-1b2a3c4d5e6f.ics"
+        );
+        for uid in ["", "..", "../../etc/passwd", "/tmp/x", "a/b", "a@b.com"] {
+            assert_eq!(ical_file_name(&task(uid)), "task-7.ics", "{uid:?}");
+        }
+    }
+}
@@ -0,0 +1,303 @@
+use std::io::{Read, Write};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    config::config,
+    model::{Task, TaskState},
+};
+
+// Taskwarrior has no notion of blocked tasks, so they are exported as pending with this tag
+const BLOCKED_TAG: &str = "blocked";
+// Taskwarrior project and priority are kept as tags with these prefixes
+const PROJECT_TAG: &str = "project:";
+const PRIORITY_TAG: &str = "pri:";
+
+// Task as found in output of `task export` and accepted by `task import`
+#[derive(Serialize, Deserialize)]
+struct TwTask {
+    uuid: String,
+    description: String,
+    status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    entry: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    modified: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    start: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    due: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    annotations: Vec<Annotation>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    depends: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Annotation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    entry: Option<String>,
+    description: String,
+}
+
+fn format_date(t: &DateTime<Utc>) -> String {
+    t.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn parse_date(s: &Option<String>) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(s.as_ref()?, "%Y%m%dT%H%M%SZ")
+        .ok()
+        .map(|t| t.and_utc())
+}
+
+// Expects tasks to have UUIDs assigned
+pub(crate) fn export(tasks: &[Task], mut out: impl Write) -> Result<()> {
+    let tasks: Vec<_> = tasks.iter().map(to_taskwarrior).collect();
+    serde_json::to_writer_pretty(&mut out, &tasks)?;
+    writeln!(out)?;
+    out.flush()?;
+    Ok(())
+}
+
+// taskwarrior_uuid returns task's UUID. Taskwarrior accepts only real UUIDs, so other ids (like
+// UIDs of imported iCalendar tasks) are turned into name-based UUID, the same on every export
+fn taskwarrior_uuid(task: &Task) -> String {
+    match task.uuid.as_deref() {
+        Some(id) => Uuid::parse_str(id)
+            .unwrap_or_else(|_| Uuid::new_v5(&Uuid::NAMESPACE_OID, id.as_bytes()))
+            .to_string(),
+        None => Uuid::new_v4().to_string(),
+    }
+}
+
+fn to_taskwarrior(task: &Task) -> TwTask {
+    let mut tw = TwTask {
+        uuid: taskwarrior_uuid(task),
+        description: task.title.replace('\n', " "),
+        status: "pending".to_string(),
+        entry: task.created_at.as_ref().map(format_date),
+        modified: task.updated_at.as_ref().map(format_date),
+        start: None,
+        end: None,
+        due: task
+            .due
+            .map(|d| format_date(&d.and_hms_opt(0, 0, 0).unwrap().and_utc())),
+        project: None,
+        priority: None,
+        tags: Vec::new(),
+        annotations: Vec::new(),
+        depends: Vec::new(),
+    };
+    for tag in &task.tags {
+        if let Some(project) = tag.strip_prefix(PROJECT_TAG) {
+            tw.project = Some(project.to_string());
+        } else if let Some(priority) = tag.strip_prefix(PRIORITY_TAG) {
+            tw.priority = Some(priority.to_string());
+        } else if *tag == config().archive_tag && task.state != TaskState::Done {
+            tw.status = "deleted".to_string();
+            tw.end = tw.modified.clone();
+        } else {
+            tw.tags.push(tag.replace(' ', "_"));
+        }
+    }
+    match task.state {
+        TaskState::Todo => {}
+        TaskState::InProgress => tw.start = tw.modified.clone(),
+        TaskState::Blocked => tw.tags.push(BLOCKED_TAG.to_string()),
+        TaskState::Done => {
+            tw.status = "completed".to_string();
+            tw.end = task
+                .completed_at
+                .as_ref()
+                .map(format_date)
+                .or(tw.modified.clone());
+        }
+    }
+    if let Some(description) = &task.description {
+        tw.annotations.push(Annotation {
+            entry: tw.modified.clone(),
+            description: description.clone(),
+        });
+    }
+    tw
+}
+
+pub(crate) fn import(input: impl Read) -> Result<Vec<Task>> {
+    let tasks: Vec<TwTask> =
+        serde_json::from_reader(input).context("parsing Taskwarrior export")?;
+    Ok(tasks
+        .into_iter()
+        // recurring task templates are not real tasks, their instances are exported separately
+        .filter(|tw| tw.status != "recurring")
+        .map(from_taskwarrior)
+        .collect())
+}
+
+fn from_taskwarrior(tw: TwTask) -> Task {
+    let blocked = tw.tags.iter().any(|t| t == BLOCKED_TAG);
+    let mut tags: Vec<String> = tw.tags.into_iter().filter(|t| t != BLOCKED_TAG).collect();
+    tags.extend(tw.project.map(|p| format!("{PROJECT_TAG}{p}")));
+    tags.extend(tw.priority.map(|p| format!("{PRIORITY_TAG}{p}")));
+
+    let state = match tw.status.as_str() {
+        "completed" => TaskState::Done,
+        "waiting" => TaskState::Blocked,
+        _ if blocked || !tw.depends.is_empty() => TaskState::Blocked,
+        _ if tw.start.is_some() => TaskState::InProgress,
+        _ => TaskState::Todo,
+    };
+    if tw.status == "deleted" {
+        tags.push(config().archive_tag.clone());
+    }
+
+    let description = tw
+        .annotations
+        .into_iter()
+        .map(|a| a.description)
+        .collect::<Vec<_>>()
+        .join("\n");
+    Task {
+        state,
+        title: tw.description,
+        description: (!description.is_empty()).then_some(description),
+        tags,
+        created_at: parse_date(&tw.entry),
+        updated_at: parse_date(&tw.modified),
+        completed_at: parse_date(&tw.end),
+        uuid: Some(tw.uuid),
+        due: parse_date(&tw.due).map(|t| t.date_naive()),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn import_one(json: &str) -> Task {
+        let mut tasks = import(format!("[{json}]").as_bytes()).unwrap();
+        assert_eq!(tasks.len(), 1);
+        tasks.remove(0)
+    }
+
+    #[test]
+    fn status_mapping() {
+        let uuid = r#""uuid": "6fa4b0a2-3e0d-4a1c-9c1e-1b2a3c4d5e6f", "description": "t""#;
+        let cases = [
+            (r#""status": "pending""#, TaskState::Todo),
+            (r#""status": "completed""#, TaskState::Done),
+            (r#""status": "waiting""#, TaskState::Blocked),
+            (
+                r#""status": "pending", "tags": ["blocked"]"#,
+                TaskState::Blocked,
+            ),
+            (
+                r#""status": "pending", "depends": ["x"]"#,
+                TaskState::Blocked,
+            ),
+            (
+                r#""status": "pending", "start": "20260101T000000Z""#,
+                TaskState::InProgress,
+            ),
+        ];
+        for (fields, state) in cases {
+            let task = import_one(&format!("{{{uuid}, {fields}}}"));
+            assert_eq!(task.state, state, "{fields}");
+            assert!(task.tags.is_empty(), "{fields}");
+        }
+
+        let task = import_one(&format!(r#"{{{uuid}, "status": "deleted"}}"#));
+        assert_eq!(task.tags, vec![config().archive_tag.clone()]);
+    }
+
+    #[test]
+    fn recurring_templates_are_skipped() {
+        let json = r#"[
+            {"uuid": "a", "description": "template", "status": "recurring"},
+            {"uuid": "b", "description": "instance", "status": "pending"}
+        ]"#;
+        let tasks = import(json.as_bytes()).unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "instance");
+    }
+
+    #[test]
+    fn round_trip() {
+        let time = |s| {
+            NaiveDateTime::parse_from_str(s, "%Y%m%dT%H%M%SZ")
+                .unwrap()
+                .and_utc()
+        };
+        let tasks = vec![
+            Task {
+                state: TaskState::Blocked,
+                title: "Wait for review".to_string(),
+                description: Some("Ping on Monday".to_string()),
+                tags: vec![
+                    "project:home".to_string(),
+                    "pri:H".to_string(),
+                    "work".to_string(),
+                ],
+                created_at: Some(time("20260101T100000Z")),
+                updated_at: Some(time("20260102T100000Z")),
+                uuid: Some("6fa4b0a2-3e0d-4a1c-9c1e-1b2a3c4d5e6f".to_string()),
+                due: chrono::NaiveDate::from_ymd_opt(2026, 2, 1),
+                ..Default::default()
+            },
+            Task {
+                state: TaskState::Done,
+                title: "Done already".to_string(),
+                created_at: Some(time("20260101T100000Z")),
+                updated_at: Some(time("20260103T100000Z")),
+                completed_at: Some(time("20260103T090000Z")),
+                uuid: Some("0b6e5f3c-58a2-4b86-a2b4-6a4c0c0b3e8d".to_string()),
+                ..Default::default()
+            },
+        ];
+        let mut out = Vec::new();
+        export(&tasks, &mut out).unwrap();
+        let imported = import(out.as_slice()).unwrap();
+
+        assert_eq!(imported[0].state, TaskState::Blocked);
+        assert_eq!(imported[0].tags, vec!["work", "project:home", "pri:H"]);
+        assert_eq!(
+            (
+                imported[0].title.as_str(),
+                imported[0].description.as_deref()
+            ),
+            ("Wait for review", Some("Ping on Monday"))
+        );
+        assert_eq!(imported[0].uuid, tasks[0].uuid);
+        assert_eq!(imported[0].created_at, tasks[0].created_at);
+        assert_eq!(imported[0].due, tasks[0].due);
+        assert_eq!(imported[1].state, TaskState::Done);
+        assert_eq!(imported[1].completed_at, tasks[1].completed_at);
+    }
+
+    #[test]
+    fn uuids_are_always_valid() {
+        let mut task = Task::default();
+        assert!(Uuid::parse_str(&taskwarrior_uuid(&task)).is_ok());
+
+        task.uuid = Some("20260101T100000Z-1@example.com".to_string());
+        let uuid = taskwarrior_uuid(&task);
+        assert!(Uuid::parse_str(&uuid).is_ok());
+        // the same id gives the same UUID on every export
+        assert_eq!(taskwarrior_uuid(&task), uuid);
+
+        let valid = "6fa4b0a2-3e0d-4a1c-9c1e-1b2a3c4d5e6f";
+        task.uuid = Some(valid.to_string());
+        assert_eq!(taskwarrior_uuid(&task), valid);
+    }
+}
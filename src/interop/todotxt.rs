@@ -0,0 +1,244 @@
+use std::io::{BufRead, BufReader, Read, Write};
+
+use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::model::{Task, TaskState};
+
+// +project becomes plain "project" tag, @context is kept as "@context" tag to tell them apart.
+// Priority is kept as a tag with this prefix, e.g. "pri:A"
+const PRIORITY_TAG: &str = "pri:";
+// Lanes other than TODO and Done don't exist in todo.txt, so they are stored as "lane:<name>" key
+const LANE_KEY: &str = "lane:";
+const DUE_KEY: &str = "due:";
+
+// todo.txt is a single line per task, so descriptions are not exported
+pub(crate) fn export(tasks: &[Task], mut out: impl Write) -> Result<()> {
+    for task in tasks {
+        writeln!(out, "{}", format_task(task))?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+pub(crate) fn import(input: impl Read) -> Result<Vec<Task>> {
+    let mut tasks = Vec::new();
+    for (idx, line) in BufReader::new(input).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        tasks.push(parse_task(&line).with_context(|| format!("line {}", idx + 1))?);
+    }
+    Ok(tasks)
+}
+
+fn format_date(t: &DateTime<Utc>) -> String {
+    t.format("%Y-%m-%d").to_string()
+}
+
+fn format_task(task: &Task) -> String {
+    let mut parts = Vec::new();
+    // only single letter priorities can be written as "(A)"
+    let priority = task.tags.iter().find_map(|t| {
+        t.strip_prefix(PRIORITY_TAG)
+            .filter(|p| p.len() == 1 && p.chars().all(|c| c.is_ascii_uppercase()))
+    });
+
+    if task.state == TaskState::Done {
+        parts.push("x".to_string());
+        if let Some(completed_at) = task.completed_at.as_ref().or(task.updated_at.as_ref()) {
+            parts.push(format_date(completed_at));
+            if let Some(created_at) = &task.created_at {
+                parts.push(format_date(created_at));
+            }
+        }
+    } else {
+        if let Some(p) = priority {
+            parts.push(format!("({p})"));
+        }
+        if let Some(created_at) = &task.created_at {
+            parts.push(format_date(created_at));
+        }
+    }
+    parts.push(task.title.replace('\n', " "));
+
+    for tag in &task.tags {
+        if let Some(p) = tag.strip_prefix(PRIORITY_TAG) {
+            // priority of completed tasks is kept as tag, following todo.txt convention, and
+            // so are priorities that are not single letters
+            if task.state == TaskState::Done || Some(p) != priority {
+                parts.push(tag.replace(' ', "_"));
+            }
+        } else if tag.starts_with('@') {
+            parts.push(tag.replace(' ', "_"));
+        } else {
+            parts.push(format!("+{}", tag.replace(' ', "_")));
+        }
+    }
+    if let Some(due) = task.due {
+        parts.push(format!("{DUE_KEY}{}", due.format("%Y-%m-%d")));
+    }
+    if matches!(task.state, TaskState::InProgress | TaskState::Blocked) {
+        let lane: &str = task.state.into();
+        parts.push(format!(
+            "{LANE_KEY}{}",
+            lane.to_lowercase().replace(' ', "-")
+        ));
+    }
+    parts.join(" ")
+}
+
+fn parse_date(word: Option<&&str>) -> Option<DateTime<Utc>> {
+    let date = NaiveDate::parse_from_str(word?, "%Y-%m-%d").ok()?;
+    Some(date.and_hms_opt(0, 0, 0).unwrap().and_utc())
+}
+
+fn parse_task(line: &str) -> Result<Task> {
+    let mut task = Task::default();
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let mut words = &words[..];
+
+    if words.first() == Some(&"x") {
+        task.state = TaskState::Done;
+        words = &words[1..];
+        if let Some(completed_at) = parse_date(words.first()) {
+            task.completed_at = Some(completed_at);
+            task.updated_at = Some(completed_at);
+            words = &words[1..];
+        }
+    } else if let Some(p) = words.first().and_then(|w| {
+        w.strip_prefix('(')
+            .and_then(|w| w.strip_suffix(')'))
+            .filter(|p| p.len() == 1 && p.chars().all(|c| c.is_ascii_uppercase()))
+    }) {
+        task.tags.push(format!("{PRIORITY_TAG}{p}"));
+        words = &words[1..];
+    }
+    if let Some(created_at) = parse_date(words.first()) {
+        task.created_at = Some(created_at);
+        words = &words[1..];
+    }
+
+    let mut title = Vec::new();
+    for word in words {
+        if let Some(project) = word.strip_prefix('+')
+            && !project.is_empty()
+        {
+            task.tags.push(project.to_string());
+        } else if word.starts_with('@') && word.len() > 1 {
+            task.tags.push(word.to_string());
+        } else if word.starts_with(PRIORITY_TAG) {
+            if !task.tags.iter().any(|t| t.starts_with(PRIORITY_TAG)) {
+                task.tags.push(word.to_string());
+            }
+        } else if let Some(due) = word.strip_prefix(DUE_KEY)
+            && let Ok(due) = NaiveDate::parse_from_str(due, "%Y-%m-%d")
+        {
+            task.due = Some(due);
+        } else if let Some(lane) = word.strip_prefix(LANE_KEY)
+            && task.state != TaskState::Done
+        {
+            task.state = lane.replace('-', " ").parse()?;
+        } else {
+            title.push(*word);
+        }
+    }
+    if title.is_empty() {
+        return Err(anyhow!("empty title"));
+    }
+    task.title = title.join(" ");
+    Ok(task)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> DateTime<Utc> {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+    }
+
+    #[test]
+    fn parse_priority_projects_and_contexts() {
+        let task = parse_task("(B) 2026-01-02 Call mom +family @phone due:2026-02-01").unwrap();
+        assert_eq!(task.state, TaskState::Todo);
+        assert_eq!(task.title, "Call mom");
+        assert_eq!(task.tags, vec!["pri:B", "family", "@phone"]);
+        assert_eq!(task.created_at, Some(date("2026-01-02")));
+        assert_eq!(task.due, NaiveDate::from_ymd_opt(2026, 2, 1));
+    }
+
+    #[test]
+    fn parse_completed() {
+        let task = parse_task("x 2026-03-04 2026-03-01 Pay bills pri:A").unwrap();
+        assert_eq!(task.state, TaskState::Done);
+        assert_eq!(task.title, "Pay bills");
+        assert_eq!(task.completed_at, Some(date("2026-03-04")));
+        assert_eq!(task.created_at, Some(date("2026-03-01")));
+        assert_eq!(task.tags, vec!["pri:A"]);
+    }
+
+    #[test]
+    fn empty_title_is_error() {
+        assert!(parse_task("x 2026-01-01 +project").is_err());
+        let err = import("first\n\n(A) @home\n".as_bytes()).err().unwrap();
+        assert_eq!(format!("{err:#}"), "line 3: empty title");
+    }
+
+    #[test]
+    fn round_trip() {
+        let tasks = vec![
+            Task {
+                state: TaskState::Done,
+                title: "Ship it".to_string(),
+                tags: vec!["pri:A".to_string(), "release".to_string()],
+                created_at: Some(date("2026-01-01")),
+                completed_at: Some(date("2026-01-05")),
+                ..Default::default()
+            },
+            Task {
+                state: TaskState::InProgress,
+                title: "Write docs".to_string(),
+                tags: vec![
+                    "pri:C".to_string(),
+                    "@desk".to_string(),
+                    "my project".to_string(),
+                ],
+                created_at: Some(date("2026-01-02")),
+                due: NaiveDate::from_ymd_opt(2026, 1, 10),
+                ..Default::default()
+            },
+        ];
+        let mut out = Vec::new();
+        export(&tasks, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(
+            text,
+            "x 2026-01-05 2026-01-01 Ship it pri:A +release\n\
+             (C) 2026-01-02 Write docs @desk +my_project due:2026-01-10 lane:in-progress\n"
+        );
+
+        let imported = import(text.as_bytes()).unwrap();
+        assert_eq!(imported[0].state, TaskState::Done);
+        assert_eq!(imported[0].tags, tasks[0].tags);
+        assert_eq!(imported[0].completed_at, tasks[0].completed_at);
+        assert_eq!(imported[1].state, TaskState::InProgress);
+        assert_eq!(imported[1].tags, vec!["pri:C", "@desk", "my_project"]);
+        assert_eq!(imported[1].due, tasks[1].due);
+    }
+
+    #[test]
+    fn non_letter_priority_is_kept_as_tag() {
+        let task = Task {
+            title: "Plan".to_string(),
+            tags: vec!["pri:high".to_string(), "pri:B".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(format_task(&task), "(B) Plan pri:high");
+    }
+}
@@ -0,0 +1,229 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::Read,
+};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::{
+    config::config,
+    model::{Task, TaskState},
+};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Board {
+    lists: Vec<List>,
+    cards: Vec<Card>,
+    #[serde(default)]
+    checklists: Vec<Checklist>,
+}
+
+#[derive(Deserialize)]
+struct List {
+    id: String,
+    name: String,
+    #[serde(default)]
+    closed: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Card {
+    id: String,
+    name: String,
+    #[serde(default)]
+    desc: String,
+    id_list: String,
+    #[serde(default)]
+    labels: Vec<Label>,
+    #[serde(default)]
+    closed: bool,
+    date_last_activity: Option<DateTime<Utc>>,
+    #[serde(default)]
+    due: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+struct Label {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    color: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Checklist {
+    id_card: String,
+    name: String,
+    #[serde(default)]
+    check_items: Vec<CheckItem>,
+}
+
+#[derive(Deserialize)]
+struct CheckItem {
+    name: String,
+    state: String,
+    #[serde(default)]
+    pos: f64,
+}
+
+// lane_for_list guesses lane from Trello list name, anything unrecognized goes to TODO
+fn lane_for_list(name: &str) -> TaskState {
+    let name = name.to_lowercase();
+    if ["done", "complete", "finished", "closed"]
+        .iter()
+        .any(|w| name.contains(w))
+    {
+        TaskState::Done
+    } else if ["progress", "doing", "wip", "review", "active"]
+        .iter()
+        .any(|w| name.contains(w))
+    {
+        TaskState::InProgress
+    } else if ["block", "wait", "hold", "stuck"]
+        .iter()
+        .any(|w| name.contains(w))
+    {
+        TaskState::Blocked
+    } else {
+        TaskState::Todo
+    }
+}
+
+// Trello object ids start with creation unix timestamp in hex
+fn created_at(id: &str) -> Option<DateTime<Utc>> {
+    let ts = i64::from_str_radix(id.get(0..8)?, 16).ok()?;
+    DateTime::from_timestamp(ts, 0)
+}
+
+pub(crate) fn import(input: impl Read) -> Result<Vec<Task>> {
+    let board: Board = serde_json::from_reader(input).context("parsing Trello board")?;
+    let lists: HashMap<_, _> = board.lists.iter().map(|l| (l.id.as_str(), l)).collect();
+    let mut checklists: HashMap<&str, Vec<&Checklist>> = HashMap::new();
+    for checklist in &board.checklists {
+        checklists
+            .entry(checklist.id_card.as_str())
+            .or_default()
+            .push(checklist);
+    }
+
+    let mut tasks = Vec::new();
+    for card in &board.cards {
+        let list = lists.get(card.id_list.as_str());
+        let mut tags: Vec<String> = card
+            .labels
+            .iter()
+            .filter_map(|l| {
+                if l.name.trim().is_empty() {
+                    l.color.clone()
+                } else {
+                    Some(l.name.trim().to_string())
+                }
+            })
+            .collect();
+        if card.closed || list.is_some_and(|l| l.closed) {
+            tags.push(config().archive_tag.clone());
+        }
+        // the same label may be attached more than once
+        let mut seen = HashSet::new();
+        tags.retain(|t| seen.insert(t.clone()));
+
+        let mut description = card.desc.trim().to_string();
+        for checklist in checklists.get(card.id.as_str()).into_iter().flatten() {
+            let mut items: Vec<_> = checklist.check_items.iter().collect();
+            items.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+            if !description.is_empty() {
+                description.push_str("\n\n");
+            }
+            description.push_str(&format!("## {}", checklist.name));
+            for item in items {
+                let mark = if item.state == "complete" { "x" } else { " " };
+                description.push_str(&format!("\n- [{mark}] {}", item.name));
+            }
+        }
+
+        tasks.push(Task {
+            state: list.map_or(TaskState::Todo, |l| lane_for_list(&l.name)),
+            title: card.name.clone(),
+            description: (!description.is_empty()).then_some(description),
+            tags,
+            created_at: created_at(&card.id),
+            updated_at: card.date_last_activity,
+            due: card.due.map(|d| d.date_naive()),
+            ..Default::default()
+        });
+    }
+    Ok(tasks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lanes_from_list_names() {
+        assert_eq!(lane_for_list("Backlog"), TaskState::Todo);
+        assert_eq!(lane_for_list("Doing"), TaskState::InProgress);
+        assert_eq!(lane_for_list("Code review"), TaskState::InProgress);
+        assert_eq!(lane_for_list("On hold"), TaskState::Blocked);
+        assert_eq!(lane_for_list("Done ✔"), TaskState::Done);
+    }
+
+    #[test]
+    fn import_board() {
+        let json = r#"{
+            "lists": [
+                {"id": "l1", "name": "In progress"},
+                {"id": "l2", "name": "Old", "closed": true}
+            ],
+            "cards": [
+                {
+                    "id": "65a1b2c3d4e5f60718293a4b",
+                    "name": "Card",
+                    "desc": "Details",
+                    "idList": "l1",
+                    "labels": [
+                        {"name": "bug", "color": "red"},
+                        {"name": "", "color": "green"},
+                        {"name": " bug ", "color": "red"}
+                    ],
+                    "dateLastActivity": "2026-01-02T10:00:00.000Z",
+                    "due": "2026-02-01T12:00:00.000Z"
+                },
+                {"id": "c2", "name": "Closed card", "idList": "l1", "closed": true},
+                {"id": "c3", "name": "In closed list", "idList": "l2"}
+            ],
+            "checklists": [
+                {
+                    "idCard": "65a1b2c3d4e5f60718293a4b",
+                    "name": "Steps",
+                    "checkItems": [
+                        {"name": "second", "state": "incomplete", "pos": 2},
+                        {"name": "first", "state": "complete", "pos": 1}
+                    ]
+                }
+            ]
+        }"#;
+        let tasks = import(json.as_bytes()).unwrap();
+        assert_eq!(tasks.len(), 3);
+
+        let task = &tasks[0];
+        assert_eq!(task.state, TaskState::InProgress);
+        assert_eq!(task.tags, vec!["bug", "green"]);
+        assert_eq!(
+            task.description.as_deref(),
+            Some("Details\n\n## Steps\n- [x] first\n- [ ] second")
+        );
+        assert_eq!(task.created_at, DateTime::from_timestamp(0x65a1b2c3, 0));
+        assert_eq!(task.due, chrono::NaiveDate::from_ymd_opt(2026, 2, 1));
+
+        let archive = vec![config().archive_tag.clone()];
+        assert_eq!(tasks[1].tags, archive);
+        assert_eq!(tasks[2].tags, archive);
+        assert_eq!(tasks[2].state, TaskState::Todo);
+    }
+}
@@ -0,0 +1,498 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{self, Display},
+    str::FromStr,
+    sync::LazyLock,
+};
+
+use anyhow::anyhow;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::{
+    config::{KeyBinding, config},
+    model::SelectedPane,
+};
+
+static KEYMAP: LazyLock<Keymap> = LazyLock::new(|| {
+    Keymap::new(&config().keys).expect("key bindings are validated when config is loaded")
+});
+
+// keymap returns key bindings from config, with defaults for actions that were not rebound
+pub(crate) fn keymap() -> &'static Keymap {
+    &KEYMAP
+}
+
+// Scope is a set of actions that are active at the same time, so their keys must not overlap
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub(crate) enum Scope {
+    Main,
+    Task,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub(crate) enum Action {
+    Help,
+    TaskHelp,
+    Command,
+    Quit,
+    NewTask,
+    OpenTask,
+    ArchiveTask,
+    MoveTodo,
+    MoveInProgress,
+    MoveBlocked,
+    MoveDone,
+    NextPane,
+    PrevPane,
+    NextLane,
+    PrevLane,
+    Down,
+    Up,
+    ToggleTag,
+    NextTheme,
+    TogglePreviewPane,
+    ToggleTagsPane,
+    ToggleTable,
+    SortTable,
+    ReverseSort,
+    NextSwimlane,
+    PrevSwimlane,
+    ToggleSwimlane,
+    ToggleCalendar,
+    ToggleCalendarWeek,
+    PrevMonth,
+    NextMonth,
+    ToggleStats,
+    NextField,
+    PrevField,
+    CloseTask,
+    SaveTask,
+    EditDescription,
+    EditTask,
+    TogglePreview,
+}
+
+impl Action {
+    // All actions in the order they are listed in hints
+    pub(crate) const ALL: [Action; 39] = [
+        Action::Help,
+        Action::Command,
+        Action::Quit,
+        Action::NewTask,
+        Action::OpenTask,
+        Action::ArchiveTask,
+        Action::MoveTodo,
+        Action::MoveInProgress,
+        Action::MoveBlocked,
+        Action::MoveDone,
+        Action::NextPane,
+        Action::PrevPane,
+        Action::NextLane,
+        Action::PrevLane,
+        Action::Down,
+        Action::Up,
+        Action::ToggleTag,
+        Action::NextTheme,
+        Action::TogglePreviewPane,
+        Action::ToggleTagsPane,
+        Action::ToggleTable,
+        Action::SortTable,
+        Action::ReverseSort,
+        Action::NextSwimlane,
+        Action::PrevSwimlane,
+        Action::ToggleSwimlane,
+        Action::ToggleCalendar,
+        Action::ToggleCalendarWeek,
+        Action::PrevMonth,
+        Action::NextMonth,
+        Action::ToggleStats,
+        Action::TaskHelp,
+        Action::NextField,
+        Action::PrevField,
+        Action::CloseTask,
+        Action::SaveTask,
+        Action::EditDescription,
+        Action::EditTask,
+        Action::TogglePreview,
+    ];
+
+    // name is used to refer to action in config
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Action::Help => "help",
+            Action::TaskHelp => "task_help",
+            Action::Command => "command",
+            Action::Quit => "quit",
+            Action::NewTask => "new_task",
+            Action::OpenTask => "open_task",
+            Action::ArchiveTask => "archive_task",
+            Action::MoveTodo => "move_todo",
+            Action::MoveInProgress => "move_in_progress",
+            Action::MoveBlocked => "move_blocked",
+            Action::MoveDone => "move_done",
+            Action::NextPane => "next_pane",
+            Action::PrevPane => "prev_pane",
+            Action::NextLane => "next_lane",
+            Action::PrevLane => "prev_lane",
+            Action::Down => "down",
+            Action::Up => "up",
+            Action::ToggleTag => "toggle_tag",
+            Action::NextTheme => "next_theme",
+            Action::TogglePreviewPane => "toggle_preview_pane",
+            Action::ToggleTagsPane => "toggle_tags_pane",
+            Action::ToggleTable => "toggle_table",
+            Action::SortTable => "sort_table",
+            Action::ReverseSort => "reverse_sort",
+            Action::NextSwimlane => "next_swimlane",
+            Action::PrevSwimlane => "prev_swimlane",
+            Action::ToggleSwimlane => "toggle_swimlane",
+            Action::ToggleCalendar => "toggle_calendar",
+            Action::ToggleCalendarWeek => "toggle_calendar_week",
+            Action::PrevMonth => "prev_month",
+            Action::NextMonth => "next_month",
+            Action::ToggleStats => "toggle_stats",
+            Action::NextField => "next_field",
+            Action::PrevField => "prev_field",
+            Action::CloseTask => "close_task",
+            Action::SaveTask => "save_task",
+            Action::EditDescription => "edit_description",
+            Action::EditTask => "edit_task",
+            Action::TogglePreview => "toggle_preview",
+        }
+    }
+
+    pub(crate) fn description(self) -> &'static str {
+        match self {
+            Action::Help | Action::TaskHelp => "help",
+            Action::Command => "command line",
+            Action::Quit => "quit",
+            Action::NewTask => "new task",
+            Action::OpenTask => "open task",
+            Action::ArchiveTask => "archive task",
+            Action::MoveTodo => "move to TODO",
+            Action::MoveInProgress => "move to In progress",
+            Action::MoveBlocked => "move to Blocked",
+            Action::MoveDone => "move to Done",
+            Action::NextPane => "next pane",
+            Action::PrevPane => "previous pane",
+            Action::NextLane => "next lane",
+            Action::PrevLane => "previous lane",
+            Action::Down => "next item",
+            Action::Up => "previous item",
+            Action::ToggleTag => "toggle tag filter",
+            Action::NextTheme => "switch color theme",
+            Action::TogglePreviewPane => "show or hide preview of selected task",
+            Action::ToggleTagsPane => "show or hide tags pane",
+            Action::ToggleTable => "switch between board and table",
+            Action::SortTable => "sort table by next column",
+            Action::ReverseSort => "reverse table sort order",
+            Action::NextSwimlane => "next swimlane",
+            Action::PrevSwimlane => "previous swimlane",
+            Action::ToggleSwimlane => "collapse or expand swimlane",
+            Action::ToggleCalendar => "switch between board and calendar",
+            Action::ToggleCalendarWeek => "switch calendar between month and week",
+            Action::PrevMonth => "previous month in calendar",
+            Action::NextMonth => "next month in calendar",
+            Action::ToggleStats => "switch between board and statistics",
+            Action::NextField => "next field",
+            Action::PrevField => "previous field",
+            Action::CloseTask => "close without saving",
+            Action::SaveTask => "save and close",
+            Action::EditDescription => "edit description in $EDITOR",
+            Action::EditTask => "edit whole task in $EDITOR",
+            Action::TogglePreview => "switch description between Markdown preview and editor",
+        }
+    }
+
+    pub(crate) fn scope(self) -> Scope {
+        match self {
+            Action::TaskHelp
+            | Action::NextField
+            | Action::PrevField
+            | Action::CloseTask
+            | Action::SaveTask
+            | Action::EditDescription
+            | Action::EditTask
+            | Action::TogglePreview => Scope::Task,
+            _ => Scope::Main,
+        }
+    }
+
+    // pane returns pane of main view action is limited to, if any
+    pub(crate) fn pane(self) -> Option<SelectedPane> {
+        match self {
+            Action::OpenTask => Some(SelectedPane::Lanes),
+            Action::ToggleTag => Some(SelectedPane::Tags),
+            _ => None,
+        }
+    }
+
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Action::Help => &["?"],
+            Action::TaskHelp => &["f1"],
+            Action::Command => &[":"],
+            Action::Quit => &["q"],
+            Action::NewTask => &["n"],
+            Action::OpenTask => &["enter", "e"],
+            Action::ArchiveTask => &["a"],
+            Action::MoveTodo => &["1"],
+            Action::MoveInProgress => &["2"],
+            Action::MoveBlocked => &["3"],
+            Action::MoveDone => &["4"],
+            Action::NextPane => &["tab"],
+            Action::PrevPane => &["shift+tab"],
+            Action::NextLane => &["right", "l"],
+            Action::PrevLane => &["left", "h"],
+            Action::Down => &["down", "j"],
+            Action::Up => &["up", "k"],
+            Action::ToggleTag => &["space"],
+            Action::NextTheme => &["t"],
+            Action::TogglePreviewPane => &["p"],
+            Action::ToggleTagsPane => &["T"],
+            Action::ToggleTable => &["v"],
+            Action::SortTable => &["s"],
+            Action::ReverseSort => &["S"],
+            Action::NextSwimlane => &["J"],
+            Action::PrevSwimlane => &["K"],
+            Action::ToggleSwimlane => &["z"],
+            Action::ToggleCalendar => &["c"],
+            Action::ToggleCalendarWeek => &["w"],
+            Action::PrevMonth => &["pageup"],
+            Action::NextMonth => &["pagedown"],
+            Action::ToggleStats => &["d"],
+            Action::NextField => &["tab"],
+            Action::PrevField => &["shift+tab"],
+            Action::CloseTask => &["esc"],
+            Action::SaveTask => &["ctrl+s"],
+            Action::EditDescription => &["ctrl+o"],
+            Action::EditTask => &["alt+o"],
+            Action::TogglePreview => &["f2"],
+        }
+    }
+}
+
+impl FromStr for Action {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Action::ALL
+            .into_iter()
+            .find(|a| a.name() == s)
+            .ok_or_else(|| anyhow!("unknown action {s:?}"))
+    }
+}
+
+// Key is a key with modifiers, normalized so that it can be compared with key events:
+// shifted characters are kept as uppercase ones without SHIFT modifier
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub(crate) struct Key {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl Key {
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers =
+            modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        let code = match code {
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            code => code,
+        };
+        if matches!(code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Self { code, modifiers }
+    }
+
+    // is_text tells if key is used for typing, so it can't be bound in task view
+    fn is_text(&self) -> bool {
+        matches!(self.code, KeyCode::Char(_))
+            && !self
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+    }
+}
+
+impl From<KeyEvent> for Key {
+    fn from(event: KeyEvent) -> Self {
+        Self::new(event.code, event.modifiers)
+    }
+}
+
+// Keys are written like "q", "Q", "ctrl+s", "alt+enter", "shift+tab", "space", "f1"
+impl FromStr for Key {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = s;
+        while let Some((modifier, r)) = rest.split_once('+')
+            && !r.is_empty()
+        {
+            modifiers |= match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(anyhow!("unknown modifier {modifier:?} in key {s:?}")),
+            };
+            rest = r;
+        }
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                f => match f.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                    Some(n @ 1..=24) => KeyCode::F(n),
+                    _ => return Err(anyhow!("unknown key {s:?}")),
+                },
+            },
+        };
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            code => write!(f, "{code:?}"),
+        }
+    }
+}
+
+pub(crate) struct Keymap {
+    bindings: HashMap<Action, Vec<Key>>,
+    actions: HashMap<(Scope, Key), Action>,
+}
+
+impl Keymap {
+    // new builds keymap from defaults overridden by configured bindings. All problems found,
+    // like unknown actions, invalid keys or keys bound to several actions, are returned at once
+    pub(crate) fn new(config: &BTreeMap<String, KeyBinding>) -> Result<Self, Vec<String>> {
+        let mut errors = Vec::new();
+        let mut bindings: HashMap<Action, Vec<Key>> = Action::ALL
+            .into_iter()
+            .map(|a| {
+                (
+                    a,
+                    a.default_keys()
+                        .iter()
+                        .map(|k| k.parse().unwrap())
+                        .collect(),
+                )
+            })
+            .collect();
+        for (name, binding) in config {
+            let action: Action = match name.parse() {
+                Ok(action) => action,
+                Err(e) => {
+                    errors.push(e.to_string());
+                    continue;
+                }
+            };
+            let keys = match binding {
+                KeyBinding::One(key) => std::slice::from_ref(key),
+                KeyBinding::Many(keys) => keys.as_slice(),
+            };
+            let mut parsed = Vec::new();
+            for key in keys {
+                match key.parse::<Key>() {
+                    Ok(key) if action.scope() == Scope::Task && key.is_text() => errors.push(
+                        format!("{name}: key {key} is used for typing in task view, add ctrl or alt to it"),
+                    ),
+                    Ok(key) => parsed.push(key),
+                    Err(e) => errors.push(format!("{name}: {e}")),
+                }
+            }
+            bindings.insert(action, parsed);
+        }
+
+        let mut actions = HashMap::new();
+        for action in Action::ALL {
+            for key in &bindings[&action] {
+                if let Some(other) = actions.insert((action.scope(), *key), action)
+                    && other != action
+                {
+                    errors.push(format!(
+                        "key {key} is bound to both {} and {}",
+                        other.name(),
+                        action.name()
+                    ));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(Self { bindings, actions })
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub(crate) fn action(&self, scope: Scope, key: KeyEvent) -> Option<Action> {
+        self.actions.get(&(scope, Key::from(key))).copied()
+    }
+
+    pub(crate) fn keys(&self, action: Action) -> &[Key] {
+        &self.bindings[&action]
+    }
+
+    // bindings lists actions available in given scope and pane, with keys bound to them
+    pub(crate) fn bindings(
+        &self,
+        scope: Scope,
+        pane: &SelectedPane,
+    ) -> impl Iterator<Item = (Action, &[Key])> {
+        Action::ALL
+            .into_iter()
+            .filter(move |a| a.scope() == scope && a.pane().is_none_or(|p| p == *pane))
+            .map(|a| (a, self.keys(a)))
+            .filter(|(_, keys)| !keys.is_empty())
+    }
+
+    // hint lists bound keys of all actions available in given scope and pane,
+    // e.g. "<q> quit, <Enter>/<e> open task"
+    pub(crate) fn hint(&self, scope: Scope, pane: &SelectedPane) -> String {
+        self.bindings(scope, pane)
+            .map(|(action, keys)| {
+                let keys: Vec<_> = keys.iter().map(|k| format!("<{k}>")).collect();
+                format!("{} {}", keys.join("/"), action.description())
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
@@ -1,41 +1,95 @@
 use ratatui::{
     buffer::Buffer,
-    layout::{Constraint, Layout, Rect},
+    layout::{Constraint, Layout, Margin, Position, Rect},
     style::{Modifier, Style, Stylize},
     text::Line,
     widgets::{Block, BorderType, StatefulWidget, Widget},
 };
 use tui_widget_list::{ListBuilder, ListState, ListView};
 
-use crate::{color_scheme::COLOR_SCHEME, model::Task};
+use unicode_width::UnicodeWidthStr;
 
-struct LaneItem {
-    title: String,
-    tags: String,
+use crate::{
+    color_scheme::colors,
+    config::config,
+    model::Task,
+    util::{truncate, wrap},
+};
+
+struct LaneItem<'a> {
+    task: &'a Task,
+    height: u16,
     style: Style,
+    tags_style: Style,
 }
 
-impl Widget for LaneItem {
-    fn render(mut self, area: Rect, buf: &mut Buffer) {
-        let areas = Layout::horizontal([
-            Constraint::Min(3),
-            Constraint::Length(self.tags.len() as u16),
-        ])
-        .split(area);
-        if (areas[0].width as usize) < self.title.len() {
-            self.title.truncate(areas[0].width as usize - 1);
-            self.title.push('>');
+impl Widget for LaneItem<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        buf.set_style(area, self.style);
+        let width = area.width as usize;
+        let tags: Vec<_> = self
+            .task
+            .tags
+            .iter()
+            .filter(|t| !t.is_empty())
+            .cloned()
+            .collect();
+        let tags = if tags.is_empty() {
+            String::new()
+        } else {
+            format!("[{}]", tags.join(", "))
+        };
+
+        if self.height == 1 {
+            // tags give way to title on narrow lanes, but take at least half of line if needed
+            let tags_width = tags.width().min(
+                width
+                    .saturating_sub(self.task.title.width() + 1)
+                    .max(width / 2),
+            );
+            let areas =
+                Layout::horizontal([Constraint::Fill(1), Constraint::Length(tags_width as u16)])
+                    .split(area);
+            let title = truncate(&self.task.title, areas[0].width as usize);
+            Line::styled(title, self.style).render(areas[0], buf);
+            Line::styled(truncate(&tags, tags_width), self.tags_style.bold())
+                .right_aligned()
+                .render(areas[1], buf);
+            return;
+        }
+
+        // title wraps over all lines but the last one, which has tags, and the one before it,
+        // reserved for first line of description on cards high enough
+        let has_description = self.height >= 3;
+        let title_lines = self.height as usize - 1 - usize::from(has_description);
+        let mut lines: Vec<_> = wrap(&self.task.title, width, title_lines)
+            .into_iter()
+            .map(|l| Line::styled(l, self.style))
+            .collect();
+        lines.resize(title_lines, Line::default());
+        if has_description {
+            let first_line = self
+                .task
+                .description
+                .as_deref()
+                .and_then(|d| d.lines().map(str::trim).find(|l| !l.is_empty()))
+                .unwrap_or_default();
+            lines.push(Line::styled(truncate(first_line, width), self.style.dim()));
+        }
+        lines.push(Line::styled(truncate(&tags, width), self.tags_style.bold()));
+        for (line, row) in lines.into_iter().zip(area.rows()) {
+            line.render(row, buf);
         }
-        Line::styled(self.title, self.style).render(areas[0], buf);
-        Line::styled(self.tags, self.style.bold())
-            .right_aligned()
-            .render(areas[1], buf);
     }
 }
 
 pub(crate) struct LaneState {
     pub(crate) list_state: ListState,
     pub(crate) selected: bool,
+    // lane area as it was last rendered, including borders
+    pub(crate) area: Rect,
+    // height of task cards, in lines
+    pub(crate) item_height: u16,
 }
 
 impl LaneState {
@@ -43,7 +97,21 @@ impl LaneState {
         Self {
             selected: false,
             list_state: ListState::default(),
+            area: Rect::default(),
+            item_height: 1,
+        }
+    }
+
+    // item_at returns index of task shown at given position, given that lane has that many tasks
+    pub(crate) fn item_at(&self, pos: Position, tasks: usize) -> Option<usize> {
+        let list_area = self.area.inner(Margin::new(1, 1));
+        if !list_area.contains(pos) {
+            return None;
         }
+        // first card shown may be partially scrolled out
+        let row = (pos.y - list_area.y + self.list_state.scroll_truncation()) / self.item_height;
+        let idx = self.list_state.scroll_offset_index() + row as usize;
+        (idx < tasks).then_some(idx)
     }
 }
 
@@ -65,43 +133,42 @@ impl<'a> StatefulWidget for &LaneWidget<'a> {
         if state.list_state.selected.is_none() {
             state.list_state.next();
         }
+        let colors = colors();
+        let height = config().layout.card_height;
+        state.item_height = height;
         let builder = ListBuilder::new(|context| {
             let task = &self.tasks[context.index];
-            let mut style = Style::default()
-                .fg(COLOR_SCHEME.text_fg)
-                .bg(COLOR_SCHEME.text_bg);
+            let mut style = colors.text();
+            let mut tags_style = colors.tag();
             if context.is_selected && state.selected && !self.inactive {
-                style = style.fg(COLOR_SCHEME.cursor_fg).bg(COLOR_SCHEME.cursor_bg);
+                style = colors.cursor();
+                tags_style = style;
             }
             let item = LaneItem {
-                title: task.title.clone(),
-                tags: format!("[{}]", task.tags.join(", ")),
+                task,
+                height,
                 style,
+                tags_style,
             };
-            (item, 1)
+            (item, height)
         });
         let list = ListView::new(builder, self.tasks.len());
         let mut block = Block::bordered()
             .title(String::from(self.title))
             .title_alignment(ratatui::layout::Alignment::Center);
 
-        let block_border_style = Style::default()
-            .fg(COLOR_SCHEME.text_fg)
-            .bg(COLOR_SCHEME.text_bg);
-        let mut block_title_style = Style::default()
-            .fg(COLOR_SCHEME.lane_title_fg)
-            .bg(COLOR_SCHEME.lane_title_bg);
+        let mut block_border_style = colors.border();
+        let mut block_title_style = colors.lane_title();
         if state.selected && !self.inactive {
             block = block.border_type(BorderType::Double);
-            block_title_style = block_title_style
-                .add_modifier(Modifier::BOLD)
-                .fg(COLOR_SCHEME.lane_active_title_fg)
-                .bg(COLOR_SCHEME.lane_active_title_bg);
+            block_border_style = colors.active_border();
+            block_title_style = colors.lane_active_title().add_modifier(Modifier::BOLD);
         }
         block = block
             .title_style(block_title_style)
             .border_style(block_border_style);
         let list_area = block.inner(area);
+        state.area = area;
         block.render(area, buf);
         list.render(list_area, buf, &mut state.list_state);
     }
@@ -1,9 +1,27 @@
 pub mod app;
+pub mod config;
+pub mod heatmap;
+pub mod interop;
+pub mod workspace;
 
+pub(crate) mod calendar_widget;
 pub(crate) mod color_scheme;
+pub(crate) mod command;
+pub(crate) mod db;
+pub(crate) mod editor;
 pub(crate) mod error_widget;
+pub(crate) mod help_widget;
+pub(crate) mod keymap;
 pub(crate) mod lane_widget;
+pub(crate) mod markdown;
 pub(crate) mod model;
+pub(crate) mod preview_widget;
 pub(crate) mod selectlist_widget;
+pub(crate) mod state;
+pub(crate) mod stats;
+pub(crate) mod stats_widget;
+pub(crate) mod swimlanes;
+pub(crate) mod table_widget;
 pub(crate) mod task_widget;
+pub(crate) mod terminal;
 pub(crate) mod util;
@@ -1,15 +1,178 @@
-use anyhow::Result;
-use awdy::app::App;
-use clap::{Arg, Command};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use awdy::{
+    app::App,
+    config::Config,
+    heatmap,
+    interop::{self, Format, ImportOptions},
+    workspace::{self, DB_FILE, WORKSPACE_DIR},
+};
+use clap::{Arg, ArgAction, ArgMatches, Command, builder::PossibleValuesParser};
 
 fn main() -> Result<()> {
     let matches = Command::new("awdy")
         .arg(
             Arg::new("db")
                 .short('d')
-                .default_value("~/.config/awdy/awdy.db"),
+                .global(true)
+                .help(format!(
+                    "Database to use. By default, {WORKSPACE_DIR}/{DB_FILE} is looked up in current and parent directories, falling back to {DB_FILE} in user data directory"
+                )),
+        )
+        .arg(
+            Arg::new("config")
+                .long("config")
+                .short('c')
+                .global(true)
+                .help(format!(
+                    "Config file, {} by default",
+                    Config::default_path().map_or_else(
+                        || "config.toml in user config directory".to_string(),
+                        |p| p.display().to_string()
+                    )
+                )),
+        )
+        .subcommand(
+            Command::new("init")
+                .about(format!("Create workspace database in {WORKSPACE_DIR} directory of current git repository or current directory")),
+        )
+        .subcommand(
+            Command::new("merge-stray")
+                .about(format!("Find {DB_FILE} files left around by older versions and merge them into current database"))
+                .arg(
+                    Arg::new("dir")
+                        .help("Directory to search in, home directory by default"),
+                )
+                .arg(
+                    Arg::new("dry-run")
+                        .long("dry-run")
+                        .short('n')
+                        .action(ArgAction::SetTrue)
+                        .help("Only list found files"),
+                ),
+        )
+        .subcommand(
+            Command::new("export")
+                .about("Export all tasks")
+                .arg(
+                    Arg::new("format")
+                        .long("format")
+                        .short('f')
+                        .value_parser(PossibleValuesParser::new(Format::EXPORT_NAMES))
+                        .default_value("csv"),
+                )
+                .arg(
+                    Arg::new("output")
+                        .long("output")
+                        .short('o')
+                        .help("Output file, stdout by default"),
+                ),
+        )
+        .subcommand(
+            Command::new("heatmap")
+                .about("Print heatmap of tasks completed each day during the last year")
+                .arg(
+                    Arg::new("tag")
+                        .long("tag")
+                        .short('t')
+                        .action(ArgAction::Append)
+                        .help("Count only tasks having this tag, can be given several times"),
+                ),
+        )
+        .subcommand(
+            Command::new("import")
+                .about("Import tasks from file")
+                .arg(
+                    Arg::new("from")
+                        .long("from")
+                        .value_parser(PossibleValuesParser::new(Format::IMPORT_NAMES))
+                        .default_value("csv"),
+                )
+                .arg(
+                    Arg::new("map")
+                        .long("map")
+                        .value_name("FIELD=COLUMN")
+                        .action(ArgAction::Append)
+                        .help("Read task field (uuid, lane, title, description, tags, due, created_at, updated_at, completed_at) from given CSV column"),
+                )
+                .arg(Arg::new("file").required(true).help("Input file, - for stdin")),
         )
         .get_matches();
-    let app = App::load(matches.get_one::<String>("db").unwrap())?;
-    app.run()
+    Config::load(matches.get_one::<String>("config").map(Path::new))?.init();
+    // resolved only by commands using database, so that init does not depend on it
+    let db_path = || workspace::db_path(matches.get_one::<String>("db").map(String::as_str));
+
+    match matches.subcommand() {
+        Some(("init", _)) => {
+            let path = workspace::init()?;
+            eprintln!("initialized empty awdy database in {}", path.display());
+            Ok(())
+        }
+        Some(("merge-stray", m)) => {
+            let db_path = &db_path()?;
+            let dir = match m.get_one::<String>("dir") {
+                Some(dir) => PathBuf::from(dir),
+                None => dirs::home_dir().context("cannot determine home directory")?,
+            };
+            let stray = workspace::find_stray(&dir, db_path)?;
+            if stray.is_empty() {
+                eprintln!("no stray databases found in {}", dir.display());
+            }
+            for path in stray {
+                if m.get_flag("dry-run") {
+                    println!("{}", path.display());
+                    continue;
+                }
+                let count = workspace::merge(db_path, &path)
+                    .with_context(|| format!("merging {}", path.display()))?;
+                eprintln!(
+                    "merged {count} tasks from {} into {}",
+                    path.display(),
+                    db_path.display()
+                );
+            }
+            Ok(())
+        }
+        Some(("export", m)) => interop::export(
+            &db_path()?,
+            format(m, "format")?,
+            m.get_one::<String>("output").map(String::as_str),
+        ),
+        Some(("heatmap", m)) => {
+            let tags: Vec<_> = m
+                .get_many::<String>("tag")
+                .unwrap_or_default()
+                .cloned()
+                .collect();
+            heatmap::print(&db_path()?, &tags)
+        }
+        Some(("import", m)) => {
+            let mut options = ImportOptions::default();
+            for mapping in m.get_many::<String>("map").unwrap_or_default() {
+                let (field, column) = mapping.split_once('=').ok_or_else(|| {
+                    anyhow::anyhow!("invalid mapping {mapping:?}, expected FIELD=COLUMN")
+                })?;
+                options
+                    .columns
+                    .push((field.trim().to_string(), column.trim().to_string()));
+            }
+            let count = interop::import(
+                &db_path()?,
+                format(m, "from")?,
+                m.get_one::<String>("file").unwrap(),
+                &options,
+            )?;
+            eprintln!("imported {count} tasks");
+            Ok(())
+        }
+        _ => {
+            let app = App::load(&db_path()?)?;
+            app.run()
+        }
+    }
+}
+
+fn format(m: &ArgMatches, arg: &str) -> Result<Format> {
+    m.get_one::<String>(arg).unwrap().parse()
 }
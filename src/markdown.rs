@@ -0,0 +1,212 @@
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use ratatui::{
+    style::{Style, Stylize},
+    text::{Line, Span, Text},
+};
+
+use crate::color_scheme::colors;
+
+// render turns Markdown into styled text. Only what makes sense in terminal is supported: headings,
+// emphasis, lists with checkboxes, quotes, code and links (shown with their URLs)
+pub(crate) fn render(markdown: &str) -> Text<'static> {
+    let mut renderer = Renderer::default();
+    let options = Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    for event in Parser::new_ext(markdown, options) {
+        renderer.event(event);
+    }
+    renderer.flush();
+    Text::from(renderer.lines)
+}
+
+#[derive(Default)]
+struct Renderer {
+    lines: Vec<Line<'static>>,
+    line: Vec<Span<'static>>,
+    // inline styles, innermost last
+    styles: Vec<Style>,
+    // number of next item for each nested list, None for bullet lists
+    lists: Vec<Option<u64>>,
+    // marker of list item to be put in front of its first line
+    item_marker: Option<String>,
+    quotes: usize,
+    code_block: bool,
+    links: Vec<String>,
+}
+
+impl Renderer {
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) if self.code_block => {
+                for line in text.lines() {
+                    self.push(format!("  {line}"), accent());
+                    self.flush();
+                }
+            }
+            Event::Text(text) | Event::Html(text) | Event::InlineHtml(text) => {
+                self.push(text.to_string(), self.style())
+            }
+            Event::Code(code) => self.push(code.to_string(), self.style().patch(accent())),
+            Event::SoftBreak | Event::HardBreak => self.flush(),
+            Event::Rule => {
+                self.block_break();
+                self.push("─".repeat(20), colors().border());
+                self.flush();
+            }
+            Event::TaskListMarker(checked) => {
+                let marker = if checked { "☑ " } else { "☐ " };
+                match self.item_marker.as_mut() {
+                    Some(m) if m.ends_with("• ") => {
+                        m.truncate(m.len() - "• ".len());
+                        m.push_str(marker);
+                    }
+                    _ => self.push(marker.to_string(), Style::new()),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph => self.block_break(),
+            Tag::Heading { level, .. } => {
+                self.block_break();
+                let style = Style::new().bold();
+                self.styles.push(match level {
+                    HeadingLevel::H1 => style.underlined(),
+                    HeadingLevel::H2 => style,
+                    _ => style.italic(),
+                });
+            }
+            Tag::BlockQuote(_) => {
+                self.block_break();
+                self.quotes += 1;
+            }
+            Tag::CodeBlock(kind) => {
+                self.block_break();
+                if let CodeBlockKind::Fenced(lang) = kind
+                    && !lang.is_empty()
+                {
+                    self.push(format!("  {lang}"), colors().border());
+                    self.flush();
+                }
+                self.code_block = true;
+            }
+            Tag::List(start) => {
+                if self.lists.is_empty() {
+                    self.block_break();
+                } else {
+                    self.flush();
+                }
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.flush();
+                let indent = "  ".repeat(self.lists.len().saturating_sub(1));
+                let marker = match self.lists.last_mut() {
+                    Some(Some(n)) => {
+                        *n += 1;
+                        format!("{}. ", *n - 1)
+                    }
+                    _ => "• ".to_string(),
+                };
+                self.item_marker = Some(indent + &marker);
+            }
+            Tag::Emphasis => self.styles.push(self.style().italic()),
+            Tag::Strong => self.styles.push(self.style().bold()),
+            Tag::Strikethrough => self.styles.push(self.style().crossed_out()),
+            Tag::Link { dest_url, .. } => {
+                self.styles.push(self.style().underlined());
+                self.links.push(dest_url.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph | TagEnd::Item => self.flush(),
+            TagEnd::Heading(_) => {
+                self.styles.pop();
+                self.flush();
+            }
+            TagEnd::BlockQuote(_) => {
+                self.flush();
+                self.quotes -= 1;
+            }
+            TagEnd::CodeBlock => {
+                self.flush();
+                self.code_block = false;
+            }
+            TagEnd::List(_) => {
+                self.flush();
+                self.lists.pop();
+            }
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => {
+                self.styles.pop();
+            }
+            TagEnd::Link => {
+                self.styles.pop();
+                if let Some(url) = self.links.pop() {
+                    self.push(format!(" ({url})"), colors().border());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn style(&self) -> Style {
+        self.styles.last().copied().unwrap_or_default()
+    }
+
+    // push adds span to current line, starting the line with quote bars and list indentation
+    fn push(&mut self, text: String, style: Style) {
+        if self.line.is_empty() {
+            if self.quotes > 0 {
+                self.line
+                    .push(Span::styled("│ ".repeat(self.quotes), colors().border()));
+            }
+            match self.item_marker.take() {
+                Some(marker) => self.line.push(Span::styled(marker, accent())),
+                None if !self.lists.is_empty() => {
+                    self.line.push(Span::raw("  ".repeat(self.lists.len())))
+                }
+                None => {}
+            }
+        }
+        self.line.push(Span::styled(text, style));
+    }
+
+    fn flush(&mut self) {
+        if self.line.is_empty() && self.item_marker.is_none() {
+            return;
+        }
+        if self.line.is_empty() {
+            // item without text
+            self.push(String::new(), Style::new());
+        }
+        self.lines.push(Line::from(std::mem::take(&mut self.line)));
+    }
+
+    // block_break separates blocks with empty line
+    fn block_break(&mut self) {
+        if self.item_marker.is_some() {
+            // first block of list item goes right after its marker
+            return;
+        }
+        self.flush();
+        if self.lines.last().is_some_and(|l| l.width() > 0) {
+            self.lines.push(Line::default());
+        }
+    }
+}
+
+// accent is used for code and list markers
+fn accent() -> Style {
+    Style {
+        fg: colors().tag().fg,
+        ..Style::new()
+    }
+}
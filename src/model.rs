@@ -1,8 +1,23 @@
-use std::{collections::HashMap, fmt::Display};
+use std::{collections::HashMap, fmt::Display, str::FromStr, time::Instant};
 
+use anyhow::anyhow;
+use chrono::{DateTime, NaiveDate, Utc};
 use ratatui::crossterm::event::KeyEvent;
 
-use crate::{lane_widget::LaneState, selectlist_widget::SelectListState, task_widget::TaskView};
+use crate::{
+    calendar_widget::CalendarState,
+    command::CommandLine,
+    editor::EditTarget,
+    help_widget::HelpState,
+    interop::Format,
+    lane_widget::LaneState,
+    selectlist_widget::SelectListState,
+    state::State,
+    stats::Stats,
+    swimlanes::Swimlanes,
+    table_widget::{Column, TaskTableState},
+    task_widget::TaskView,
+};
 
 pub(crate) struct Model<'a> {
     pub(crate) tasks: HashMap<TaskState, Vec<Task>>,
@@ -14,7 +29,33 @@ pub(crate) struct Model<'a> {
     pub(crate) tags: SelectListState,
 
     pub(crate) task_view: Option<TaskView<'a>>,
+    pub(crate) help: Option<HelpState>,
+    pub(crate) command_line: Option<CommandLine>,
+    // lane task is being dragged from with mouse
+    pub(crate) drag: Option<usize>,
+    // time, lane and task of last mouse click, to detect double clicks
+    pub(crate) last_click: Option<(Instant, usize, usize)>,
+    // external editor to be opened once pending messages are processed
+    pub(crate) edit_request: Option<EditTarget>,
+    // result of last command, shown in status bar instead of hints
+    pub(crate) status: Option<String>,
+    pub(crate) state: State,
+    // board is split into rows by tags, if set
+    pub(crate) swimlanes: Option<Swimlanes>,
+    // how tasks are shown in main view
+    pub(crate) view: View,
+    pub(crate) table: TaskTableState,
+    pub(crate) calendar: CalendarState,
+    // statistics shown in dashboard, computed when it is opened
+    pub(crate) stats: Option<Stats>,
+    // tags pane visibility chosen by user, None to decide by terminal width
+    pub(crate) show_tags: Option<bool>,
+    // whether tags pane is shown, following show_tags or terminal width
+    pub(crate) tags_visible: bool,
+    // terminal width, to decide on layout before drawing
+    pub(crate) width: u16,
     pub(crate) last_error: Option<anyhow::Error>,
+    pub(crate) db_path: String,
 }
 
 #[derive(Default, Clone, PartialEq)]
@@ -24,6 +65,19 @@ pub(crate) struct Task {
     pub(crate) title: String,
     pub(crate) description: Option<String>,
     pub(crate) tags: Vec<String>,
+    pub(crate) created_at: Option<DateTime<Utc>>,
+    pub(crate) updated_at: Option<DateTime<Utc>>,
+    pub(crate) completed_at: Option<DateTime<Utc>>,
+    // stable identifier used to match tasks exchanged with other tools
+    pub(crate) uuid: Option<String>,
+    pub(crate) due: Option<NaiveDate>,
+}
+
+// Transition is task entering a lane, as recorded in task history
+pub(crate) struct Transition {
+    pub(crate) task_id: u64,
+    pub(crate) state: TaskState,
+    pub(crate) at: DateTime<Utc>,
 }
 
 #[derive(Default, PartialEq)]
@@ -32,6 +86,16 @@ pub(crate) enum SelectedPane {
     Lanes,
     Tags,
 }
+// View is the way tasks are shown in main view
+#[derive(Default, Clone, Copy, PartialEq)]
+pub(crate) enum View {
+    #[default]
+    Board,
+    Table,
+    Calendar,
+    Stats,
+}
+
 #[derive(Default, PartialEq)]
 pub(crate) enum RunningState {
     #[default]
@@ -40,7 +104,7 @@ pub(crate) enum RunningState {
     Done,
 }
 
-#[derive(Hash, Default, Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
+#[derive(Hash, Default, Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug)]
 pub(crate) enum TaskState {
     #[default]
     Todo = 0,
@@ -49,6 +113,15 @@ pub(crate) enum TaskState {
     Done = 3,
 }
 
+impl TaskState {
+    pub(crate) const ALL: [TaskState; 4] = [
+        TaskState::Todo,
+        TaskState::InProgress,
+        TaskState::Blocked,
+        TaskState::Done,
+    ];
+}
+
 impl Display for TaskState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str((*self).into())
@@ -66,6 +139,22 @@ impl From<TaskState> for &'static str {
     }
 }
 
+impl FromStr for TaskState {
+    type Err = anyhow::Error;
+
+    // accepts lane names as displayed (case-insensitive) or lane numbers 1-4
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        for state in TaskState::ALL {
+            let name: &str = state.into();
+            if s.eq_ignore_ascii_case(name) || s == (state as usize + 1).to_string() {
+                return Ok(state);
+            }
+        }
+        Err(anyhow!("unknown lane {s:?}"))
+    }
+}
+
 impl From<i32> for TaskState {
     fn from(value: i32) -> Self {
         match value {
@@ -100,6 +189,44 @@ pub(crate) enum Message {
     SaveTask,
     MoveTask(TaskState),
     ToggleTaskTag(String),
+    NextTheme,
+    ShowHelp,
+    ScrollHelp(isize),
+    CloseHelp,
+    ClickTask(usize, Option<usize>),
+    ClickTag(usize),
+    DropTask(Option<usize>),
+    ScrollLane(usize, isize),
+    OpenCommandLine,
+    CloseCommandLine,
+    CompleteCommand,
+    RunCommand,
+    OpenTaskById(u64),
+    SetFilters(Vec<String>),
+    RenameTag(String, String),
+    Export(Format, String),
+    SwitchBoard(String),
+    OpenEditor(EditTarget),
+    TogglePreview,
+    TogglePreviewPane,
+    ToggleTagsPane,
+    ToggleTable,
+    SortTable(Column),
+    SelectRow(isize),
+    ClickRow(usize),
+    SelectSwimlane(usize),
+    ToggleSwimlane(usize),
+    SetSwimlanes(Option<String>),
+    ToggleCalendar,
+    ToggleCalendarWeek,
+    SelectDay(i64),
+    SelectMonth(i32),
+    ToggleDayTasks,
+    SelectDayTask(isize),
+    OpenDayTask,
+    ToggleStats,
+    // terminal was resized to given width
+    Resize(u16),
     FocusNext,
     FocusPrev,
     CloseError,
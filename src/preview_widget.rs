@@ -0,0 +1,74 @@
+use chrono::{DateTime, Local, Utc};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Stylize,
+    text::{Line, Span, Text},
+    widgets::{Block, Paragraph, Widget, Wrap},
+};
+
+use crate::{color_scheme::colors, markdown, model::Task};
+
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+// PreviewWidget shows everything about selected task, next to lanes
+pub(crate) struct PreviewWidget<'a> {
+    pub(crate) task: Option<&'a Task>,
+}
+
+impl Widget for &PreviewWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let colors = colors();
+        let block = Block::bordered()
+            .title("Preview")
+            .title_style(colors.lane_title())
+            .border_style(colors.border())
+            .style(colors.text());
+        let Some(task) = self.task else {
+            Paragraph::new(Line::from("No task selected").italic())
+                .block(block)
+                .render(area, buf);
+            return;
+        };
+
+        let mut text = Text::from(Line::from(task.title.clone()).bold());
+        let mut info = vec![Span::raw(task.state.to_string())];
+        if let Some(id) = task.id {
+            info.insert(0, Span::raw(format!("#{id} · ")));
+        }
+        if let Some(due) = task.due {
+            info.push(Span::raw(format!(" · due {due}")));
+        }
+        text.push_line(Line::from(info));
+        let tags: Vec<_> = task
+            .tags
+            .iter()
+            .filter(|t| !t.is_empty())
+            .cloned()
+            .collect();
+        if !tags.is_empty() {
+            text.push_line(Line::styled(tags.join(", "), colors.tag().bold()));
+        }
+        for (name, time) in [
+            ("created", task.created_at),
+            ("updated", task.updated_at),
+            ("completed", task.completed_at),
+        ] {
+            if let Some(time) = time {
+                text.push_line(Line::from(format!("{name} {}", local_time(time))).dim());
+            }
+        }
+        if let Some(description) = &task.description {
+            text.push_line(Line::default());
+            text.extend(markdown::render(description));
+        }
+        Paragraph::new(text)
+            .wrap(Wrap { trim: false })
+            .block(block)
+            .render(area, buf);
+    }
+}
+
+fn local_time(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local).format(TIME_FORMAT).to_string()
+}
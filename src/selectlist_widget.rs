@@ -1,15 +1,29 @@
 use ratatui::{
-    style::{Style, Stylize},
+    layout::{Position, Rect},
+    style::Stylize,
     text::Text,
     widgets::{Block, BorderType, StatefulWidget, Widget},
 };
 use tui_widget_list::{ListBuilder, ListState, ListView};
 
-use crate::color_scheme::COLOR_SCHEME;
+use crate::color_scheme::colors;
 
 pub(crate) struct SelectListState {
     pub(crate) list_state: ListState,
     pub(crate) items: Vec<(String, bool, &'static str)>, //3rd element is symbol shown if item is not selected
+    // list area as it was last rendered
+    pub(crate) area: Rect,
+}
+
+impl SelectListState {
+    // item_at returns index of item shown at given position, if any
+    pub(crate) fn item_at(&self, pos: Position) -> Option<usize> {
+        if !self.area.contains(pos) {
+            return None;
+        }
+        let idx = self.list_state.scroll_offset_index() + (pos.y - self.area.y) as usize;
+        (idx < self.items.len()).then_some(idx)
+    }
 }
 
 pub(crate) struct SelectList {
@@ -29,11 +43,10 @@ impl StatefulWidget for &SelectList {
         if state.list_state.selected.is_none() {
             state.list_state.next();
         }
+        let colors = colors();
         let builder = ListBuilder::new(|context| {
             let item = &state.items[context.index];
-            let mut style = Style::default()
-                .fg(COLOR_SCHEME.text_fg)
-                .bg(COLOR_SCHEME.text_bg);
+            let mut style = colors.text();
             if item.1 {
                 style = style.bold();
             }
@@ -44,10 +57,7 @@ impl StatefulWidget for &SelectList {
             ))
             .style(style);
             if !self.inactive && context.is_selected {
-                item.style = item
-                    .style
-                    .fg(COLOR_SCHEME.cursor_fg)
-                    .bg(COLOR_SCHEME.cursor_bg);
+                item.style = item.style.patch(colors.cursor());
             }
             (item, 1)
         });
@@ -58,22 +68,17 @@ impl StatefulWidget for &SelectList {
             // .borders(Borders::RIGHT | Borders::TOP)
             .border_type(BorderType::Thick);
 
-        let mut block_title_style = Style::default()
-            .bold()
-            .fg(COLOR_SCHEME.lane_title_fg)
-            .bg(COLOR_SCHEME.lane_title_bg);
-        let block_border_style = Style::default()
-            .fg(COLOR_SCHEME.text_fg)
-            .bg(COLOR_SCHEME.text_bg);
+        let mut block_title_style = colors.lane_title().bold();
+        let mut block_border_style = colors.border();
         if !self.inactive {
-            block_title_style = block_title_style
-                .fg(COLOR_SCHEME.lane_active_title_fg)
-                .bg(COLOR_SCHEME.lane_active_title_bg);
+            block_title_style = colors.lane_active_title().bold();
+            block_border_style = colors.active_border();
         }
         block = block
             .title_style(block_title_style)
             .border_style(block_border_style);
         let list_area = block.inner(area);
+        state.area = list_area;
         block.render(area, buf);
         list.render(list_area, buf, &mut state.list_state);
     }
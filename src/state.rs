@@ -0,0 +1,40 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+
+// State is UI state remembered between sessions. Unlike config, it is written by awdy itself
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct State {
+    // preview pane is shown in main view
+    pub(crate) preview: bool,
+}
+
+impl State {
+    // path is state.toml in user state directory ($XDG_STATE_HOME/awdy on Linux), or local data
+    // directory on systems without one
+    fn path() -> Option<PathBuf> {
+        dirs::state_dir()
+            .or_else(dirs::data_local_dir)
+            .map(|dir| dir.join("awdy").join("state.toml"))
+    }
+
+    // load reads saved state. Missing or broken file is not worth failing over, defaults are
+    // used then
+    pub(crate) fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|text| toml::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn save(&self) -> Result<()> {
+        let path = Self::path().ok_or_else(|| anyhow!("cannot determine user state directory"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+        }
+        let text = toml::to_string(self).context("serializing state")?;
+        fs::write(&path, text).with_context(|| format!("writing {}", path.display()))
+    }
+}
@@ -0,0 +1,104 @@
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, TimeDelta, Utc};
+
+use crate::{
+    heatmap::Heatmap,
+    model::{TaskState, Transition},
+};
+
+// Number of weeks statistics are collected for, including the current one
+pub(crate) const WEEKS: usize = 12;
+
+pub(crate) struct Stats {
+    // days covered, from Monday WEEKS ago to today
+    pub(crate) days: Vec<NaiveDate>,
+    // number of tasks in each lane by the end of each day
+    pub(crate) flow: Vec<[u64; 4]>,
+    // first day of each week and number of tasks moved to Done during it
+    pub(crate) throughput: Vec<(NaiveDate, u64)>,
+    // average time from starting work on task (moving it out of TODO) to moving it to Done,
+    // for tasks completed during covered period
+    pub(crate) cycle_time: Option<TimeDelta>,
+    // number of tasks cycle time is averaged over
+    pub(crate) cycle_tasks: usize,
+    pub(crate) heatmap: Heatmap,
+}
+
+impl Stats {
+    // new computes statistics from history of given tasks. History must be ordered by task and time
+    pub(crate) fn new(history: &[Transition], tasks: &HashSet<u64>) -> Self {
+        let today = Local::now().date_naive();
+        let this_week = today - Days::new(today.weekday().num_days_from_monday() as u64);
+        let start = this_week - Days::new(7 * (WEEKS as u64 - 1));
+        let days: Vec<_> = start.iter_days().take_while(|d| *d <= today).collect();
+
+        let mut by_task: BTreeMap<u64, Vec<&Transition>> = BTreeMap::new();
+        for t in history {
+            if tasks.contains(&t.task_id) {
+                by_task.entry(t.task_id).or_default().push(t);
+            }
+        }
+
+        let mut flow = vec![[0; 4]; days.len()];
+        let mut throughput: Vec<_> = (0..WEEKS)
+            .map(|w| (start + Days::new(7 * w as u64), 0))
+            .collect();
+        let mut cycle_times = Vec::new();
+        for transitions in by_task.values() {
+            // lane task is in by the end of each day
+            let mut next = 0;
+            let mut state = None;
+            for (day, counts) in days.iter().zip(flow.iter_mut()) {
+                while let Some(t) = transitions.get(next)
+                    && local_date(t.at) <= *day
+                {
+                    state = Some(t.state);
+                    next += 1;
+                }
+                if let Some(state) = state {
+                    counts[state as usize] += 1;
+                }
+            }
+
+            let mut started = None;
+            for t in transitions {
+                match t.state {
+                    TaskState::Todo => {}
+                    TaskState::InProgress | TaskState::Blocked => {
+                        started.get_or_insert(t.at);
+                    }
+                    TaskState::Done => {
+                        let date = local_date(t.at);
+                        if date < start {
+                            continue;
+                        }
+                        let week = (date - start).num_days() as usize / 7;
+                        if let Some((_, count)) = throughput.get_mut(week) {
+                            *count += 1;
+                        }
+                        if let Some(started) = started.take() {
+                            cycle_times.push(t.at - started);
+                        }
+                    }
+                }
+            }
+        }
+
+        let cycle_tasks = cycle_times.len();
+        let cycle_time =
+            (cycle_tasks > 0).then(|| cycle_times.iter().sum::<TimeDelta>() / cycle_tasks as i32);
+        Self {
+            days,
+            flow,
+            throughput,
+            cycle_time,
+            cycle_tasks,
+            heatmap: Heatmap::new(history, tasks),
+        }
+    }
+}
+
+fn local_date(time: DateTime<Utc>) -> NaiveDate {
+    time.with_timezone(&Local).date_naive()
+}
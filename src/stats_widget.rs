@@ -0,0 +1,209 @@
+use chrono::TimeDelta;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::Stylize,
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{
+        Axis, Bar, BarChart, BarGroup, Block, Chart, Dataset, GraphType, LegendPosition, Paragraph,
+        Widget,
+    },
+};
+
+use crate::{
+    color_scheme::colors,
+    heatmap::{self, Heatmap},
+    model::TaskState,
+    stats::{Stats, WEEKS},
+};
+
+const DATE_FORMAT: &str = "%b %-d";
+// Month labels, weekdays and legend, with borders
+const HEATMAP_HEIGHT: u16 = 12;
+
+// StatsWidget shows dashboard with cumulative flow, throughput and cycle time of tasks
+pub(crate) struct StatsWidget<'a> {
+    pub(crate) stats: &'a Stats,
+}
+
+impl Widget for &StatsWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let [flow_area, bottom, heatmap_area] = Layout::vertical([
+            Constraint::Fill(3),
+            Constraint::Fill(2),
+            Constraint::Length(HEATMAP_HEIGHT),
+        ])
+        .areas(area);
+        let [throughput_area, summary_area] =
+            Layout::horizontal([Constraint::Fill(2), Constraint::Fill(1)]).areas(bottom);
+        self.render_flow(flow_area, buf);
+        self.render_throughput(throughput_area, buf);
+        self.render_summary(summary_area, buf);
+        self.render_heatmap(heatmap_area, buf);
+    }
+}
+
+impl StatsWidget<'_> {
+    // render_flow draws lanes stacked on top of each other, Done being the lowest one, so that
+    // the top line is total number of tasks
+    fn render_flow(&self, area: Rect, buf: &mut Buffer) {
+        let colors = colors();
+        let stats = self.stats;
+        let points: Vec<Vec<(f64, f64)>> = TaskState::ALL
+            .iter()
+            .map(|state| {
+                stats
+                    .flow
+                    .iter()
+                    .enumerate()
+                    .map(|(day, counts)| {
+                        let stacked: u64 = counts[*state as usize..].iter().sum();
+                        (day as f64, stacked as f64)
+                    })
+                    .collect()
+            })
+            .collect();
+        let datasets = TaskState::ALL
+            .iter()
+            .zip(points.iter())
+            .map(|(state, points)| {
+                Dataset::default()
+                    .name(state.to_string())
+                    .marker(Marker::Braille)
+                    .graph_type(GraphType::Line)
+                    .style(colors.lane(*state))
+                    .data(points)
+            })
+            .collect();
+
+        let max = stats
+            .flow
+            .iter()
+            .map(|counts| counts.iter().sum::<u64>())
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let last = stats.days.len().saturating_sub(1);
+        let x_labels: Vec<_> = [0, last / 2, last]
+            .iter()
+            .filter_map(|&idx| stats.days.get(idx))
+            .map(|day| day.format(DATE_FORMAT).to_string())
+            .collect();
+        let y_labels = [0, max / 2, max].map(|n| n.to_string());
+
+        Chart::new(datasets)
+            .block(block(format!("Cumulative flow, last {WEEKS} weeks")))
+            .style(colors.text())
+            .x_axis(
+                Axis::default()
+                    .bounds([0.0, last.max(1) as f64])
+                    .labels(x_labels)
+                    .style(colors.border()),
+            )
+            .y_axis(
+                Axis::default()
+                    .bounds([0.0, max as f64])
+                    .labels(y_labels)
+                    .style(colors.border()),
+            )
+            .legend_position(Some(LegendPosition::TopLeft))
+            .hidden_legend_constraints((Constraint::Percentage(50), Constraint::Percentage(50)))
+            .render(area, buf);
+    }
+
+    fn render_throughput(&self, area: Rect, buf: &mut Buffer) {
+        let colors = colors();
+        let style = colors.lane(TaskState::Done);
+        let bars: Vec<_> = self
+            .stats
+            .throughput
+            .iter()
+            .map(|(week, count)| {
+                Bar::default()
+                    .value(*count)
+                    .label(Line::from(week.format(DATE_FORMAT).to_string()))
+            })
+            .collect();
+        let block = block("Done per week".to_string());
+        let width = block.inner(area).width;
+        let bar_width = ((width + 1) / WEEKS as u16).saturating_sub(1).max(1);
+        BarChart::default()
+            .block(block)
+            .style(colors.text())
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(bar_width)
+            .bar_gap(1)
+            .bar_style(style)
+            .value_style(style.reversed())
+            .label_style(colors.border())
+            .render(area, buf);
+    }
+
+    fn render_summary(&self, area: Rect, buf: &mut Buffer) {
+        let colors = colors();
+        let stats = self.stats;
+        let cycle_time = match stats.cycle_time {
+            Some(time) => format!("{} ({} tasks)", duration(time), stats.cycle_tasks),
+            None => "no tasks completed".to_string(),
+        };
+        let done: u64 = stats.throughput.iter().map(|(_, count)| count).sum();
+        let mut lines = vec![
+            Line::from("Average cycle time").bold(),
+            Line::from(cycle_time),
+            Line::default(),
+            Line::from("Average throughput").bold(),
+            Line::from(format!("{:.1} tasks per week", done as f64 / WEEKS as f64)),
+            Line::default(),
+            Line::from("Now").bold(),
+        ];
+        if let Some(counts) = stats.flow.last() {
+            for state in TaskState::ALL {
+                lines.push(Line::from(vec![
+                    Span::styled("■ ", colors.lane(state)),
+                    Span::raw(format!("{state}: {}", counts[state as usize])),
+                ]));
+            }
+        }
+        Paragraph::new(lines)
+            .style(colors.text())
+            .block(block("Summary".to_string()))
+            .render(area, buf);
+    }
+
+    // render_heatmap shows as many weeks of the last year as fit
+    fn render_heatmap(&self, area: Rect, buf: &mut Buffer) {
+        let colors = colors();
+        let block = block("Completed tasks".to_string());
+        let width = block.inner(area).width as usize;
+        let weeks = (1..=heatmap::WEEKS)
+            .rev()
+            .find(|weeks| Heatmap::width(*weeks) <= width)
+            .unwrap_or(1);
+        let lines = self
+            .stats
+            .heatmap
+            .lines(weeks, colors.lane(TaskState::Done), colors.border());
+        Paragraph::new(lines)
+            .style(colors.text())
+            .block(block)
+            .render(area, buf);
+    }
+}
+
+fn block(title: String) -> Block<'static> {
+    let colors = colors();
+    Block::bordered()
+        .title(title)
+        .title_style(colors.lane_title())
+        .border_style(colors.border())
+}
+
+// duration formats time as hours when it is short, as days otherwise
+fn duration(time: TimeDelta) -> String {
+    if time.num_hours() < 48 {
+        format!("{} hours", time.num_hours())
+    } else {
+        format!("{:.1} days", time.num_seconds() as f64 / 86400.0)
+    }
+}
@@ -0,0 +1,106 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use ratatui::layout::Rect;
+
+use crate::{
+    lane_widget::LaneState,
+    model::{Task, TaskState},
+};
+
+// Swimlanes split board into rows, one per tag of chosen namespace (tags starting with given
+// prefix, like "team/"). Tasks and lanes of active row are kept in model as if there were no
+// swimlanes at all, so that everything working with lanes works with active row too
+pub(crate) struct Swimlanes {
+    pub(crate) namespace: String,
+    pub(crate) rows: Vec<Swimlane>,
+    pub(crate) active: usize,
+}
+
+pub(crate) struct Swimlane {
+    // None for row of tasks without tags of namespace
+    pub(crate) tag: Option<String>,
+    // tasks and lanes of the row, unless it is active one
+    pub(crate) tasks: HashMap<TaskState, Vec<Task>>,
+    pub(crate) lanes: Vec<LaneState>,
+    pub(crate) collapsed: bool,
+    // areas of row and its header as they were last rendered
+    pub(crate) area: Rect,
+    pub(crate) header: Rect,
+}
+
+impl Swimlanes {
+    pub(crate) fn new(namespace: String) -> Self {
+        Self {
+            namespace,
+            rows: Vec::new(),
+            active: 0,
+        }
+    }
+
+    // group puts tasks into rows by their tags. Task having several tags of namespace shows up in
+    // several rows. Active and collapsed rows stay such if they are still there
+    pub(crate) fn group(&mut self, tasks: Vec<Task>) {
+        let active = self.rows.get(self.active).map(|r| r.tag.clone());
+        let collapsed: HashSet<_> = self
+            .rows
+            .iter()
+            .filter(|r| r.collapsed)
+            .map(|r| r.tag.clone())
+            .collect();
+
+        // row of tasks without tag goes last
+        let mut groups: BTreeMap<(bool, Option<String>), Vec<Task>> = BTreeMap::new();
+        groups.insert((true, None), Vec::new());
+        for task in tasks {
+            let tags: Vec<_> = task
+                .tags
+                .iter()
+                .filter(|t| t.starts_with(&self.namespace) && t.len() > self.namespace.len())
+                .cloned()
+                .collect();
+            if tags.is_empty() {
+                groups.entry((true, None)).or_default().push(task);
+                continue;
+            }
+            for tag in tags {
+                groups
+                    .entry((false, Some(tag)))
+                    .or_default()
+                    .push(task.clone());
+            }
+        }
+
+        self.rows = groups
+            .into_iter()
+            .map(|((_, tag), group)| {
+                let mut tasks: HashMap<_, _> = TaskState::ALL
+                    .into_iter()
+                    .map(|s| (s, Vec::new()))
+                    .collect();
+                for task in group {
+                    tasks.get_mut(&task.state).unwrap().push(task);
+                }
+                Swimlane {
+                    collapsed: collapsed.contains(&tag),
+                    tag,
+                    tasks,
+                    lanes: TaskState::ALL.iter().map(|_| LaneState::new()).collect(),
+                    area: Rect::default(),
+                    header: Rect::default(),
+                }
+            })
+            .collect();
+        self.active = self
+            .rows
+            .iter()
+            .position(|r| Some(&r.tag) == active.as_ref())
+            .unwrap_or(0);
+    }
+
+    pub(crate) fn title(&self, row: usize) -> String {
+        match &self.rows[row].tag {
+            Some(tag) => tag[self.namespace.len()..].to_string(),
+            None => format!("no {} tag", self.namespace),
+        }
+    }
+}
@@ -0,0 +1,273 @@
+use std::{cmp::Ordering, collections::HashMap, str::FromStr};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Local, Utc};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Position, Rect},
+    style::{Modifier, Stylize},
+    widgets::{Block, BorderType, Cell, Row, StatefulWidget, Table, TableState},
+};
+
+use crate::{
+    color_scheme::colors,
+    model::{Task, TaskState},
+};
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum Column {
+    Id,
+    Lane,
+    Title,
+    Tags,
+    Due,
+    Created,
+    Updated,
+    Completed,
+}
+
+impl Column {
+    pub(crate) const ALL: [Column; 8] = [
+        Column::Id,
+        Column::Lane,
+        Column::Title,
+        Column::Tags,
+        Column::Due,
+        Column::Created,
+        Column::Updated,
+        Column::Completed,
+    ];
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Column::Id => "id",
+            Column::Lane => "lane",
+            Column::Title => "title",
+            Column::Tags => "tags",
+            Column::Due => "due",
+            Column::Created => "created",
+            Column::Updated => "updated",
+            Column::Completed => "completed",
+        }
+    }
+
+    fn width(self) -> Constraint {
+        match self {
+            Column::Id => Constraint::Length(6),
+            Column::Lane => Constraint::Length(12),
+            Column::Title => Constraint::Fill(3),
+            Column::Tags => Constraint::Fill(1),
+            _ => Constraint::Length(DATE_FORMAT.len() as u16 + 4),
+        }
+    }
+
+    fn cell(self, task: &Task) -> String {
+        match self {
+            Column::Id => task.id.map(|id| format!("#{id}")).unwrap_or_default(),
+            Column::Lane => task.state.to_string(),
+            Column::Title => task.title.clone(),
+            Column::Tags => tags(task),
+            Column::Due => task.due.map(|d| d.to_string()).unwrap_or_default(),
+            Column::Created => local_date(task.created_at),
+            Column::Updated => local_date(task.updated_at),
+            Column::Completed => local_date(task.completed_at),
+        }
+    }
+
+    // has_value tells if task has something in this column, tasks without value are always
+    // sorted last
+    fn has_value(self, task: &Task) -> bool {
+        match self {
+            Column::Id | Column::Lane | Column::Title => true,
+            Column::Tags => !tags(task).is_empty(),
+            Column::Due => task.due.is_some(),
+            Column::Created => task.created_at.is_some(),
+            Column::Updated => task.updated_at.is_some(),
+            Column::Completed => task.completed_at.is_some(),
+        }
+    }
+
+    fn compare(self, a: &Task, b: &Task) -> Ordering {
+        match self {
+            Column::Id => a.id.cmp(&b.id),
+            Column::Lane => a.state.cmp(&b.state),
+            Column::Title => a.title.to_lowercase().cmp(&b.title.to_lowercase()),
+            Column::Tags => tags(a).to_lowercase().cmp(&tags(b).to_lowercase()),
+            Column::Due => a.due.cmp(&b.due),
+            Column::Created => a.created_at.cmp(&b.created_at),
+            Column::Updated => a.updated_at.cmp(&b.updated_at),
+            Column::Completed => a.completed_at.cmp(&b.completed_at),
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Column::ALL
+            .into_iter()
+            .find(|c| c.name().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| anyhow!("unknown column {s:?}"))
+    }
+}
+
+fn tags(task: &Task) -> String {
+    let tags: Vec<_> = task
+        .tags
+        .iter()
+        .filter(|t| !t.is_empty())
+        .cloned()
+        .collect();
+    tags.join(", ")
+}
+
+fn local_date(time: Option<DateTime<Utc>>) -> String {
+    time.map(|t| t.with_timezone(&Local).format(DATE_FORMAT).to_string())
+        .unwrap_or_default()
+}
+
+pub(crate) struct TaskTableState {
+    pub(crate) sort: Column,
+    pub(crate) descending: bool,
+    // index of first row shown
+    offset: usize,
+    // table area as it was last rendered, including borders
+    pub(crate) area: Rect,
+}
+
+impl TaskTableState {
+    pub(crate) fn new() -> Self {
+        Self {
+            sort: Column::Id,
+            descending: false,
+            offset: 0,
+            area: Rect::default(),
+        }
+    }
+
+    // sort_by sorts rows by given column, reversing the order if they are already sorted by it
+    pub(crate) fn sort_by(&mut self, column: Column) {
+        if self.sort == column {
+            self.descending = !self.descending;
+        } else {
+            self.sort = column;
+            self.descending = false;
+        }
+    }
+
+    // rows returns lanes and indices of tasks in the order they are shown in table
+    pub(crate) fn rows(&self, tasks: &HashMap<TaskState, Vec<Task>>) -> Vec<(usize, usize)> {
+        let task = |&(lane, idx): &(usize, usize)| &tasks[&TaskState::from(lane as i32)][idx];
+        let mut rows: Vec<_> = TaskState::ALL
+            .iter()
+            .flat_map(|state| (0..tasks[state].len()).map(|idx| (*state as usize, idx)))
+            .collect();
+        rows.sort_by(|a, b| {
+            let (a, b) = (task(a), task(b));
+            let order = self.sort.compare(a, b);
+            let order = if self.descending {
+                order.reverse()
+            } else {
+                order
+            };
+            (!self.sort.has_value(a))
+                .cmp(&!self.sort.has_value(b))
+                .then(order)
+                .then(a.id.cmp(&b.id))
+        });
+        rows
+    }
+
+    // row_at returns index of row shown at given position, given that table has that many rows
+    pub(crate) fn row_at(&self, pos: Position, rows: usize) -> Option<usize> {
+        let inner = Block::bordered().inner(self.area);
+        if !inner.contains(pos) || pos.y == inner.y {
+            return None;
+        }
+        let idx = self.offset + (pos.y - inner.y - 1) as usize;
+        (idx < rows).then_some(idx)
+    }
+
+    // column_at returns column which header is shown at given position
+    pub(crate) fn column_at(&self, pos: Position) -> Option<Column> {
+        let inner = Block::bordered().inner(self.area);
+        if !inner.contains(pos) || pos.y != inner.y {
+            return None;
+        }
+        // the same layout table uses for its columns
+        let areas = Layout::horizontal(Column::ALL.map(Column::width))
+            .spacing(1)
+            .split(inner);
+        Column::ALL
+            .into_iter()
+            .zip(areas.iter())
+            .find(|(_, area)| area.contains(pos))
+            .map(|(column, _)| column)
+    }
+}
+
+// TaskTable shows tasks of all lanes as table, with selected task being the one selected on board
+pub(crate) struct TaskTable<'a> {
+    pub(crate) tasks: &'a HashMap<TaskState, Vec<Task>>,
+    // lane and index of selected task
+    pub(crate) selected: (usize, usize),
+    pub(crate) inactive: bool,
+}
+
+impl StatefulWidget for &TaskTable<'_> {
+    type State = TaskTableState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let colors = colors();
+        let rows = state.rows(self.tasks);
+        let header = Row::new(Column::ALL.map(|column| {
+            let arrow = match (column == state.sort, state.descending) {
+                (false, _) => "",
+                (true, false) => " ▲",
+                (true, true) => " ▼",
+            };
+            Cell::from(format!("{}{arrow}", column.name()))
+        }))
+        .style(colors.lane_title().bold());
+        let table_rows = rows.iter().map(|&(lane, idx)| {
+            let task = &self.tasks[&TaskState::from(lane as i32)][idx];
+            Row::new(Column::ALL.map(|column| {
+                let cell = Cell::from(column.cell(task));
+                if column == Column::Tags {
+                    cell.style(colors.tag().bold())
+                } else {
+                    cell
+                }
+            }))
+        });
+
+        let mut block = Block::bordered()
+            .title(format!("Tasks ({})", rows.len()))
+            .title_alignment(ratatui::layout::Alignment::Center)
+            .title_style(colors.lane_title())
+            .border_style(colors.border());
+        let mut highlight = colors.text();
+        if !self.inactive {
+            block = block
+                .border_type(BorderType::Double)
+                .border_style(colors.active_border())
+                .title_style(colors.lane_active_title().add_modifier(Modifier::BOLD));
+            highlight = colors.cursor();
+        }
+        let table = Table::new(table_rows, Column::ALL.map(Column::width))
+            .header(header)
+            .block(block)
+            .style(colors.text())
+            .row_highlight_style(highlight);
+
+        let mut table_state = TableState::new()
+            .with_offset(state.offset)
+            .with_selected(rows.iter().position(|r| *r == self.selected));
+        state.area = area;
+        table.render(area, buf, &mut table_state);
+        state.offset = table_state.offset();
+    }
+}
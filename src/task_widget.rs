@@ -1,18 +1,27 @@
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
 use ratatui::{
     buffer::Buffer,
-    crossterm::event::KeyEvent,
+    crossterm::event::{KeyCode, KeyEvent},
     layout::{Constraint, Layout, Margin, Rect},
     style::{Modifier, Style, Stylize},
     text::Line,
-    widgets::{Block, BorderType, Clear, Widget},
+    widgets::{Block, BorderType, Clear, Paragraph, Widget, Wrap},
 };
 use tui_textarea::{CursorMove, TextArea};
 
 use crate::{
+    config::config,
+    editor::{Document, EditTarget},
+    markdown,
     model::{Task, TaskState},
     util::{centered_rect, is_newline},
 };
 
+const DUE_FORMAT: &str = "%Y-%m-%d";
+// Lines scrolled in description preview by PgUp and PgDn
+const PREVIEW_PAGE: usize = 10;
+
 struct LabeledEdit<'a, 'b> {
     label: &'a str,
     edit: &'b TextArea<'a>,
@@ -44,12 +53,19 @@ pub(crate) struct TaskView<'a> {
     task_state: TaskState,
     pub(crate) text_areas: Vec<TextArea<'a>>,
     pub(crate) active_text_area: usize,
+    // text edited in external editor that could not be parsed, so it is opened again instead of
+    // task fields next time editor is used
+    pub(crate) failed_edit: Option<(EditTarget, String)>,
+    // description is shown as rendered Markdown instead of editor
+    preview: bool,
+    preview_scroll: usize,
 }
 
 impl<'a> TaskView<'a> {
     const TITLE: usize = 0;
     const DESCRIPTION: usize = 1;
     const TAGS: usize = 2;
+    const DUE: usize = 3;
 
     fn on_focus_change(&mut self) {
         for (i, tv) in self.text_areas.iter_mut().enumerate() {
@@ -71,15 +87,103 @@ impl<'a> TaskView<'a> {
         self.on_focus_change();
     }
 
+    pub(crate) fn toggle_preview(&mut self) {
+        self.preview = !self.preview;
+        self.preview_scroll = 0;
+    }
+
     pub(crate) fn process_event(&mut self, event: KeyEvent) {
-        if (self.active_text_area == Self::TITLE || self.active_text_area == Self::TAGS)
-            && is_newline(event)
-        {
+        if self.preview && self.active_text_area == Self::DESCRIPTION {
+            // preview is read-only, only scrolling works there
+            let lines = markdown::render(&self.field(Self::DESCRIPTION)).height();
+            self.preview_scroll = match event.code {
+                KeyCode::Down => self.preview_scroll + 1,
+                KeyCode::Up => self.preview_scroll.saturating_sub(1),
+                KeyCode::PageDown => self.preview_scroll + PREVIEW_PAGE,
+                KeyCode::PageUp => self.preview_scroll.saturating_sub(PREVIEW_PAGE),
+                KeyCode::Home => 0,
+                KeyCode::End => lines,
+                _ => self.preview_scroll,
+            }
+            .min(lines.saturating_sub(1));
+            return;
+        }
+        if self.active_text_area != Self::DESCRIPTION && is_newline(event) {
             self.next_field();
             return;
         }
         self.text_areas[self.active_text_area].input(event);
     }
+
+    fn field(&self, idx: usize) -> String {
+        self.text_areas[idx].lines().join("\n")
+    }
+
+    fn set_field(&mut self, idx: usize, text: &str) {
+        let area = &mut self.text_areas[idx];
+        area.select_all();
+        area.insert_str(text);
+    }
+
+    // edit_text returns text to be edited in external editor
+    pub(crate) fn edit_text(&mut self, target: EditTarget) -> String {
+        match self.failed_edit.take() {
+            Some((t, text)) if t == target => text,
+            _ => match target {
+                EditTarget::Description => self.field(Self::DESCRIPTION),
+                EditTarget::Task => Document {
+                    title: self.field(Self::TITLE),
+                    tags: self.field(Self::TAGS),
+                    due: self.field(Self::DUE),
+                    description: self.field(Self::DESCRIPTION),
+                }
+                .render(),
+            },
+        }
+    }
+
+    // apply_edit updates fields with text edited in external editor. If text can't be parsed,
+    // it is kept to be edited again
+    pub(crate) fn apply_edit(&mut self, target: EditTarget, text: String) -> Result<()> {
+        match target {
+            EditTarget::Description => {
+                self.set_field(Self::DESCRIPTION, text.trim_end());
+            }
+            EditTarget::Task => {
+                let doc = match Document::parse(&text).and_then(|doc| {
+                    parse_due(&doc.due)?;
+                    Ok(doc)
+                }) {
+                    Ok(doc) => doc,
+                    Err(e) => {
+                        self.failed_edit = Some((target, text));
+                        return Err(e.context("parsing edited task, open editor again to fix it"));
+                    }
+                };
+                self.set_field(Self::TITLE, &doc.title);
+                self.set_field(Self::TAGS, &doc.tags);
+                self.set_field(Self::DUE, &doc.due);
+                self.set_field(Self::DESCRIPTION, &doc.description);
+            }
+        }
+        Ok(())
+    }
+
+    // validate checks fields that may fail to convert into task
+    pub(crate) fn validate(&self) -> Result<()> {
+        parse_due(&self.text_areas[Self::DUE].lines().join(""))?;
+        Ok(())
+    }
+}
+
+fn parse_due(s: &str) -> Result<Option<NaiveDate>> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Ok(None);
+    }
+    NaiveDate::parse_from_str(s, DUE_FORMAT)
+        .map(Some)
+        .with_context(|| format!("invalid due date {s:?}, expected YYYY-MM-DD"))
 }
 
 impl From<TaskView<'_>> for Task {
@@ -102,6 +206,8 @@ impl From<TaskView<'_>> for Task {
                 Some(description.to_string())
             },
             tags,
+            due: parse_due(&v.text_areas[TaskView::DUE].lines().join("")).unwrap_or_default(),
+            ..Default::default()
         }
     }
 }
@@ -114,6 +220,11 @@ impl From<Task> for TaskView<'_> {
                 .map_or_else(Vec::new, |d| d.split("\n").map(String::from).collect()),
         );
         let mut tags_area = TextArea::new(vec![task.tags.join(", ")]);
+        let mut due_area = TextArea::new(vec![
+            task.due
+                .map(|d| d.format(DUE_FORMAT).to_string())
+                .unwrap_or_default(),
+        ]);
 
         title_area.set_cursor_line_style(Style::default());
         title_area.move_cursor(CursorMove::End);
@@ -122,6 +233,9 @@ impl From<Task> for TaskView<'_> {
         description_area.move_cursor(CursorMove::End);
         tags_area.set_cursor_line_style(Style::default());
         tags_area.move_cursor(CursorMove::End);
+        due_area.set_cursor_line_style(Style::default());
+        due_area.move_cursor(CursorMove::End);
+        due_area.set_placeholder_text("YYYY-MM-DD");
         description_area.set_block(
             Block::bordered()
                 .title("Description")
@@ -131,14 +245,34 @@ impl From<Task> for TaskView<'_> {
         let mut r = Self {
             task_id: task.id,
             task_state: task.state,
-            text_areas: vec![title_area, description_area, tags_area],
+            text_areas: vec![title_area, description_area, tags_area, due_area],
             active_text_area: 0,
+            failed_edit: None,
+            preview: false,
+            preview_scroll: 0,
         };
         r.on_focus_change();
         r
     }
 }
 
+impl TaskView<'_> {
+    fn render_preview(&self, area: Rect, buf: &mut Buffer, scroll: usize) {
+        let mut block = Block::bordered()
+            .title("Description")
+            .title_style(Style::default().bold())
+            .title_bottom(Line::from(" preview ").right_aligned());
+        if self.preview && self.active_text_area == TaskView::DESCRIPTION {
+            block = block.border_type(BorderType::Thick);
+        }
+        Paragraph::new(markdown::render(&self.field(TaskView::DESCRIPTION)))
+            .wrap(Wrap { trim: false })
+            .scroll((scroll as u16, 0))
+            .block(block)
+            .render(area, buf);
+    }
+}
+
 impl Widget for &TaskView<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let window_area = centered_rect(area, 70, 70);
@@ -153,6 +287,7 @@ impl Widget for &TaskView<'_> {
             Constraint::Max(1),
             Constraint::Fill(1),
             Constraint::Max(1),
+            Constraint::Max(1),
         ])
         .spacing(1)
         .split(task_area);
@@ -160,7 +295,24 @@ impl Widget for &TaskView<'_> {
         Clear.render(window_area, buf);
         block.render(window_area, buf);
         LabeledEdit::new("Title:", &self.text_areas[TaskView::TITLE]).render(layout[0], buf);
-        self.text_areas[TaskView::DESCRIPTION].render(layout[1], buf);
+        let split_width = config().layout.split_preview_width;
+        if self.preview {
+            self.render_preview(layout[1], buf, self.preview_scroll);
+        } else if split_width > 0 && area.width >= split_width {
+            let [edit_area, preview_area] =
+                Layout::horizontal([Constraint::Fill(1); 2]).areas(layout[1]);
+            self.text_areas[TaskView::DESCRIPTION].render(edit_area, buf);
+            // keep part of preview corresponding to cursor position in view
+            let (row, _) = self.text_areas[TaskView::DESCRIPTION].cursor();
+            let lines = self.text_areas[TaskView::DESCRIPTION].lines().len();
+            let preview_lines = markdown::render(&self.field(TaskView::DESCRIPTION)).height();
+            let scroll = (row * preview_lines / lines.max(1))
+                .saturating_sub(preview_area.height as usize / 2);
+            self.render_preview(preview_area, buf, scroll);
+        } else {
+            self.text_areas[TaskView::DESCRIPTION].render(layout[1], buf);
+        }
         LabeledEdit::new("Tags:", &self.text_areas[TaskView::TAGS]).render(layout[2], buf);
+        LabeledEdit::new("Due:", &self.text_areas[TaskView::DUE]).render(layout[3], buf);
     }
 }
@@ -0,0 +1,157 @@
+use std::{env, str::FromStr, time::Duration};
+
+use anyhow::anyhow;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum Background {
+    Light,
+    Dark,
+}
+
+// background tells whether terminal background is light or dark. Terminal is asked for its
+// background color first (OSC 11), if it does not answer in time, COLORFGBG set by some
+// terminals is checked. None if nothing is known
+pub(crate) fn background(timeout: Duration) -> Option<Background> {
+    query_background(timeout).or_else(colorfgbg_background)
+}
+
+// COLORFGBG is "fg;bg" (sometimes "fg;default;bg") with colors being indices of 16-color palette
+fn colorfgbg_background() -> Option<Background> {
+    let value = env::var("COLORFGBG").ok()?;
+    let bg: u8 = value.rsplit(';').next()?.parse().ok()?;
+    Some(match bg {
+        0..=6 | 8 => Background::Dark,
+        _ => Background::Light,
+    })
+}
+
+#[cfg(unix)]
+fn query_background(timeout: Duration) -> Option<Background> {
+    use std::{
+        fs::OpenOptions,
+        io::{IsTerminal, Read, Write},
+        time::Instant,
+    };
+
+    use crossterm::terminal;
+    use rustix::event::{PollFd, PollFlags, Timespec, poll};
+
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+    let mut tty = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()?;
+    terminal::enable_raw_mode().ok()?;
+    // Primary device attributes (DA1) are requested after background color. Virtually every
+    // terminal answers it, so there is no need to wait for the whole timeout if one
+    // does not know about OSC 11
+    let mut response = Vec::new();
+    if tty.write_all(b"\x1b]11;?\x1b\\\x1b[c").is_ok() && tty.flush().is_ok() {
+        let deadline = Instant::now() + timeout;
+        let mut buf = [0u8; 256];
+        while !has_device_attributes(&response) {
+            // tty is only read when it has input, so nothing typed later gets swallowed
+            let left = deadline.saturating_duration_since(Instant::now());
+            let Ok(left) = Timespec::try_from(left) else {
+                break;
+            };
+            let mut fds = [PollFd::new(&tty, PollFlags::IN)];
+            if !matches!(poll(&mut fds, Some(&left)), Ok(n) if n > 0) {
+                break;
+            }
+            match tty.read(&mut buf) {
+                Ok(n) if n > 0 => response.extend_from_slice(&buf[..n]),
+                _ => break,
+            }
+        }
+    }
+    let _ = terminal::disable_raw_mode();
+    parse_background(&String::from_utf8_lossy(&response))
+}
+
+#[cfg(not(unix))]
+fn query_background(_timeout: Duration) -> Option<Background> {
+    None
+}
+
+// has_device_attributes checks if response to DA1 request ("ESC [ ? ... c") was received
+fn has_device_attributes(response: &[u8]) -> bool {
+    response
+        .windows(3)
+        .position(|w| w == b"\x1b[?")
+        .is_some_and(|start| response[start..].contains(&b'c'))
+}
+
+// parse_background extracts color from "ESC ] 11 ; rgb:RRRR/GGGG/BBBB" answer, with every
+// component having 1 to 4 hex digits
+fn parse_background(response: &str) -> Option<Background> {
+    let start = response.find("]11;rgb:")? + "]11;rgb:".len();
+    let color = response[start..].split(['\x1b', '\x07']).next()?;
+    let mut components = [0.0; 3];
+    let mut parts = color.split('/');
+    for c in &mut components {
+        let part = parts.next()?;
+        if part.is_empty() || part.len() > 4 {
+            return None;
+        }
+        let max = (1u32 << (4 * part.len())) - 1;
+        *c = u32::from_str_radix(part, 16).ok()? as f64 / max as f64;
+    }
+    let [r, g, b] = components;
+    let luminance = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    Some(if luminance < 0.5 {
+        Background::Dark
+    } else {
+        Background::Light
+    })
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum ColorDepth {
+    // no colors at all, only bold and reversed text
+    Monochrome,
+    Ansi16,
+    Ansi256,
+    TrueColor,
+}
+
+impl FromStr for ColorDepth {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::Monochrome),
+            "16" => Ok(Self::Ansi16),
+            "256" => Ok(Self::Ansi256),
+            "truecolor" => Ok(Self::TrueColor),
+            _ => Err(anyhow!("unknown color depth {s:?}")),
+        }
+    }
+}
+
+// color_depth guesses what colors terminal supports from environment. NO_COLOR (https://no-color.org)
+// disables colors altogether
+pub(crate) fn color_depth() -> ColorDepth {
+    if env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+        return ColorDepth::Monochrome;
+    }
+    if let Ok(colorterm) = env::var("COLORTERM")
+        && matches!(colorterm.as_str(), "truecolor" | "24bit")
+    {
+        return ColorDepth::TrueColor;
+    }
+    match env::var("TERM") {
+        Ok(term) if term == "dumb" => ColorDepth::Monochrome,
+        Ok(term) if term.ends_with("-direct") || term.contains("truecolor") => {
+            ColorDepth::TrueColor
+        }
+        Ok(term) if term.contains("256color") => ColorDepth::Ansi256,
+        Ok(_) => ColorDepth::Ansi16,
+        // Windows terminals do not set TERM, but all of recent ones are fine with true colors
+        Err(_) if cfg!(windows) => ColorDepth::TrueColor,
+        Err(_) => ColorDepth::Ansi16,
+    }
+}
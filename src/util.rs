@@ -2,6 +2,7 @@ use ratatui::{
     crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
     layout::{Constraint, Flex, Layout, Rect},
 };
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 pub(crate) fn centered_rect(parent: Rect, horizontal_pct: u16, vertical_pct: u16) -> Rect {
     let hor = Layout::horizontal([Constraint::Percentage(horizontal_pct)]).flex(Flex::Center);
@@ -11,6 +12,79 @@ pub(crate) fn centered_rect(parent: Rect, horizontal_pct: u16, vertical_pct: u16
     area
 }
 
+// truncate cuts text to given display width, marking the cut with '>'
+pub(crate) fn truncate(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    let mut head = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w >= width {
+            break;
+        }
+        head.push(c);
+        used += w;
+    }
+    head.push('>');
+    head
+}
+
+// wrap breaks text into lines no wider than given width, at whitespace when possible. Text that
+// does not fit into max_lines is truncated
+pub(crate) fn wrap(text: &str, width: usize, max_lines: usize) -> Vec<String> {
+    if width == 0 || max_lines == 0 {
+        return Vec::new();
+    }
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let sep = usize::from(!line.is_empty());
+        if line.width() + sep + word.width() <= width {
+            if sep > 0 {
+                line.push(' ');
+            }
+            line.push_str(word);
+            continue;
+        }
+        if !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
+        }
+        let mut rest = word;
+        while rest.width() > width {
+            let (head, tail) = split_at_width(rest, width);
+            lines.push(head.to_string());
+            rest = tail;
+        }
+        line = rest.to_string();
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    if lines.len() > max_lines {
+        let rest = lines.split_off(max_lines - 1).join(" ");
+        lines.push(truncate(&rest, width));
+    }
+    lines
+}
+
+// split_at_width splits text so that head is no wider than given width, but takes at least one
+// character, so that wide characters do not get stuck
+fn split_at_width(text: &str, width: usize) -> (&str, &str) {
+    let mut used = 0;
+    for (idx, c) in text.char_indices() {
+        used += c.width().unwrap_or(0);
+        if used > width && idx > 0 {
+            return text.split_at(idx);
+        }
+    }
+    (text, "")
+}
+
 pub(crate) fn is_newline(e: KeyEvent) -> bool {
     e.code == KeyCode::Enter
         || (e.code == KeyCode::Char('m') && e.modifiers == KeyModifiers::CONTROL)
@@ -0,0 +1,123 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, anyhow};
+
+use crate::{config::config, db::Db};
+
+pub const WORKSPACE_DIR: &str = ".awdy";
+pub const DB_FILE: &str = "awdy.db";
+
+// find_workspace walks up from given directory looking for .awdy directory, like git does for .git
+pub fn find_workspace(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(WORKSPACE_DIR))
+        .find(|dir| dir.is_dir())
+}
+
+// global_db_path is database used outside of workspaces, located in user data directory
+// ($XDG_DATA_HOME/awdy/awdy.db on Linux)
+pub fn global_db_path() -> Result<PathBuf> {
+    let dir = dirs::data_dir().ok_or_else(|| anyhow!("cannot determine user data directory"))?;
+    Ok(dir.join("awdy").join(DB_FILE))
+}
+
+// db_path picks database to use: explicitly given one, the one from enclosing workspace,
+// configured one or global one
+pub fn db_path(explicit: Option<&str>) -> Result<PathBuf> {
+    if let Some(path) = explicit {
+        return Ok(expand_home(path));
+    }
+    let cwd = env::current_dir().context("getting current directory")?;
+    match (find_workspace(&cwd), &config().db) {
+        (Some(workspace), _) => Ok(workspace.join(DB_FILE)),
+        (None, Some(path)) => Ok(expand_home(path)),
+        (None, None) => global_db_path(),
+    }
+}
+
+// init creates workspace with empty database in root of current git repository,
+// or in current directory if it is not inside of one. Returns path to the database
+pub fn init() -> Result<PathBuf> {
+    let cwd = env::current_dir().context("getting current directory")?;
+    let root = cwd
+        .ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .unwrap_or(&cwd);
+    let workspace = root.join(WORKSPACE_DIR);
+    if workspace.exists() {
+        return Err(anyhow!(
+            "workspace already exists at {}",
+            workspace.display()
+        ));
+    }
+    fs::create_dir(&workspace).with_context(|| format!("creating {}", workspace.display()))?;
+    let db_path = workspace.join(DB_FILE);
+    Db::open(&db_path)?;
+    Ok(db_path)
+}
+
+// find_stray looks for awdy.db files left in random directories by older versions, which
+// fell back to current directory when database could not be opened. Hidden directories,
+// including workspaces, are not searched
+pub fn find_stray(root: &Path, db_path: &Path) -> Result<Vec<PathBuf>> {
+    let db_path = db_path.canonicalize().ok();
+    let mut found = Vec::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            // unreadable directories are of no interest
+            Err(_) => continue,
+        };
+        for entry in entries {
+            let Ok(entry) = entry else { continue };
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let path = entry.path();
+            if file_type.is_dir() && !entry.file_name().to_string_lossy().starts_with('.') {
+                dirs.push(path);
+            } else if file_type.is_file()
+                && entry.file_name() == DB_FILE
+                && path.canonicalize().ok() != db_path
+            {
+                found.push(path);
+            }
+        }
+    }
+    found.sort();
+    Ok(found)
+}
+
+// merge copies all tasks from stray database into given one in single transaction, and only then
+// renames stray database to awdy.db.merged, so it is not picked up again. Returns number of
+// merged tasks
+pub fn merge(db_path: &Path, stray: &Path) -> Result<usize> {
+    // stray database is not upgraded in place, it is only read
+    let mut tasks = Db::open_copy(stray)?.all_tasks().context("loading tasks")?;
+    Db::open(db_path)?
+        .import_tasks(&mut tasks)
+        .context("merging tasks")?;
+    let merged = stray.with_extension("db.merged");
+    fs::rename(stray, &merged).with_context(|| format!("renaming {}", stray.display()))?;
+    Ok(tasks.len())
+}
+
+pub(crate) fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+// display_path shortens path for showing to user, replacing home directory with ~
+pub(crate) fn display_path(path: &Path) -> String {
+    match dirs::home_dir().and_then(|home| path.strip_prefix(home).ok()) {
+        Some(rest) => Path::new("~").join(rest).to_string_lossy().into_owned(),
+        None => path.to_string_lossy().into_owned(),
+    }
+}